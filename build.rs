@@ -0,0 +1,473 @@
+//! Generates the constants of the `models` & `images` modules.
+//!
+//! # Models
+//!
+//! Every `models/<name>.obj` (Wavefront OBJ) becomes a `pub const <NAME>:
+//! Shape3D<V, E, F>` in the `models` module. Vertices (`v`), faces (`f`) and
+//! lines (`l`) are read, everything else (normals, texture coordinates,
+//! groups, materials...) is ignored. Faces with more than three vertices are
+//! split into triangles, the edges are the sides of the faces and lines,
+//! without duplicates.
+//!
+//! A comment line `# shape3d: <options>` in the file sets options:
+//!
+//! - `center`: move the center of the bounding box to the origin
+//! - `normalize`: scale the model to fit into the unit sphere
+//!
+//! # Images
+//!
+//! Every `images/<name>.png` or `images/<name>.pbm` becomes a `pub const
+//! <NAME>: ImageRaw<BinaryColor>` in the `images` module, 1 bit per pixel.
+//! Like on the display, light pixels are on, dark & transparent ones off. A
+//! line `<file> <options>` in `images/options.txt` sets options for that file:
+//!
+//! - `threshold=<0-255>`: pixels at least this light are on, 128 by default
+//! - `dither`: dither the gray levels (Floyd-Steinberg) instead
+//! - `invert`: dark pixels are on instead of light ones
+//!
+//! [`Shape3D`]: src/shape3d.rs
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::Write as _,
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+const MODELS: &str = "models";
+const IMAGES: &str = "images";
+/// Conversion options for the images, see the module documentation.
+const IMAGE_OPTIONS: &str = "images/options.txt";
+
+#[derive(Default)]
+struct Model {
+    vertices: Vec<[f32; 3]>,
+    edges: BTreeSet<(usize, usize)>,
+    faces: Vec<(usize, usize, usize)>,
+}
+
+fn main() {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    models(&out_dir);
+    images(&out_dir);
+}
+
+/// The files in `dir` with one of the `extensions`, sorted.
+fn files(dir: &str, extensions: &[&str]) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-changed={dir}");
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|dir| {
+            dir.map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|e| extensions.iter().any(|x| e == *x))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    for path in &paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    paths
+}
+
+/// The name of the constant for the file at `path`.
+fn const_name(path: &Path) -> String {
+    let stem = path.file_stem().unwrap().to_string_lossy();
+    let name: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+fn models(out_dir: &Path) {
+    let mut out = String::new();
+    for path in files(MODELS, &["obj"]) {
+        let model = parse(&path);
+        write_const(&mut out, &path, &model);
+    }
+    fs::write(out_dir.join("models.rs"), out).unwrap();
+}
+
+fn parse(path: &Path) -> Model {
+    let source = fs::read_to_string(path).unwrap();
+    let mut model = Model::default();
+    let (mut center, mut normalize) = (false, false);
+
+    for (n, line) in source.lines().enumerate() {
+        let fail = |msg: &str| -> ! { panic!("{}:{}: {}", path.display(), n + 1, msg) };
+
+        if let Some(options) = line.trim().strip_prefix("# shape3d:") {
+            for option in options.split_whitespace() {
+                match option {
+                    "center" => center = true,
+                    "normalize" => normalize = true,
+                    _ => fail(&format!("unknown option `{option}`")),
+                }
+            }
+            continue;
+        }
+
+        let mut words = line.split('#').next().unwrap().split_whitespace();
+        match words.next() {
+            Some("v") => {
+                let mut xyz = [0.0; 3];
+                for x in &mut xyz {
+                    *x = words
+                        .next()
+                        .and_then(|w| w.parse().ok())
+                        .unwrap_or_else(|| fail("expected 3 coordinates"));
+                }
+                model.vertices.push(xyz);
+            }
+            Some(kind @ ("f" | "l")) => {
+                let count = model.vertices.len();
+                let indices: Vec<usize> = words
+                    .map(|w| {
+                        // `v`, `v/vt`, `v//vn` or `v/vt/vn`, 1-based or
+                        // negative to count back from the last vertex.
+                        let index: isize = w
+                            .split('/')
+                            .next()
+                            .and_then(|i| i.parse().ok())
+                            .unwrap_or_else(|| fail(&format!("invalid index `{w}`")));
+                        match index {
+                            1.. if index as usize <= count => index as usize - 1,
+                            ..=-1 if index.unsigned_abs() <= count => count - index.unsigned_abs(),
+                            _ => fail(&format!("index {index} out of range")),
+                        }
+                    })
+                    .collect();
+
+                if kind == "f" {
+                    if indices.len() < 3 {
+                        fail("a face needs at least 3 vertices");
+                    }
+                    for i in 1..indices.len() - 1 {
+                        model.faces.push((indices[0], indices[i], indices[i + 1]));
+                    }
+                    let last = indices[indices.len() - 1];
+                    model.edges.insert(sorted(last, indices[0]));
+                }
+                for pair in indices.windows(2) {
+                    model.edges.insert(sorted(pair[0], pair[1]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if center && !model.vertices.is_empty() {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for v in &model.vertices {
+            for i in 0..3 {
+                min[i] = min[i].min(v[i]);
+                max[i] = max[i].max(v[i]);
+            }
+        }
+        for v in &mut model.vertices {
+            for i in 0..3 {
+                v[i] -= (min[i] + max[i]) / 2.0;
+            }
+        }
+    }
+
+    if normalize {
+        let radius = model
+            .vertices
+            .iter()
+            .map(|v| v.iter().map(|x| x * x).sum::<f32>().sqrt())
+            .fold(0.0, f32::max);
+        if radius > 0.0 {
+            for x in model.vertices.iter_mut().flatten() {
+                *x /= radius;
+            }
+        }
+    }
+
+    model
+}
+
+fn sorted(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn write_const(out: &mut String, path: &Path, model: &Model) {
+    let name = const_name(path);
+    let stem = path.file_stem().unwrap().to_string_lossy();
+
+    let (v, e, f) = (model.vertices.len(), model.edges.len(), model.faces.len());
+
+    writeln!(out, "/// Imported from `{MODELS}/{stem}.obj`.").unwrap();
+    writeln!(out, "pub const {name}: Shape3D<{v}, {e}, {f}> = Shape3D {{").unwrap();
+    writeln!(out, "    vertices: [").unwrap();
+    for [x, y, z] in &model.vertices {
+        writeln!(out, "        point![{x:?}, {y:?}, {z:?}],").unwrap();
+    }
+    writeln!(out, "    ],").unwrap();
+    writeln!(
+        out,
+        "    edges: {:?},",
+        model.edges.iter().collect::<Vec<_>>()
+    )
+    .unwrap();
+    writeln!(out, "    faces: {:?},", model.faces).unwrap();
+    writeln!(out, "}};").unwrap();
+}
+
+/// How an image is turned into 1 bit per pixel.
+struct ImageOptions {
+    threshold: u8,
+    dither: bool,
+    invert: bool,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 128,
+            dither: false,
+            invert: false,
+        }
+    }
+}
+
+/// An image in shades of gray, from 0 (black) to 255 (white).
+struct Gray {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+fn images(out_dir: &Path) {
+    println!("cargo:rerun-if-changed={IMAGE_OPTIONS}");
+    let mut options = image_options();
+
+    let mut out = String::new();
+    for path in files(IMAGES, &["png", "pbm"]) {
+        let file = path.file_name().unwrap().to_string_lossy().into_owned();
+        let image = match path.extension().unwrap().to_str() {
+            Some("png") => read_png(&path),
+            _ => read_pbm(&path),
+        };
+        let options = options.remove(&file).unwrap_or_default();
+        write_image(&mut out, &path, &image, &options);
+    }
+    if let Some(file) = options.keys().next() {
+        panic!("{IMAGE_OPTIONS}: no image `{file}` in `{IMAGES}`");
+    }
+
+    fs::write(out_dir.join("images.rs"), out).unwrap();
+}
+
+fn image_options() -> BTreeMap<String, ImageOptions> {
+    let source = fs::read_to_string(IMAGE_OPTIONS).unwrap_or_default();
+    let mut all = BTreeMap::new();
+
+    for (n, line) in source.lines().enumerate() {
+        let fail = |msg: &str| -> ! { panic!("{}:{}: {}", IMAGE_OPTIONS, n + 1, msg) };
+
+        let mut words = line.split('#').next().unwrap().split_whitespace();
+        let Some(file) = words.next() else {
+            continue;
+        };
+
+        let mut options = ImageOptions::default();
+        for option in words {
+            match option.split_once('=') {
+                Some(("threshold", level)) => {
+                    options.threshold = level
+                        .parse()
+                        .unwrap_or_else(|_| fail(&format!("invalid threshold `{level}`")));
+                }
+                None if option == "dither" => options.dither = true,
+                None if option == "invert" => options.invert = true,
+                _ => fail(&format!("unknown option `{option}`")),
+            }
+        }
+        all.insert(file.to_owned(), options);
+    }
+
+    all
+}
+
+fn read_png(path: &Path) -> Gray {
+    let fail = |msg: &dyn std::fmt::Display| -> ! { panic!("{}: {}", path.display(), msg) };
+
+    let mut decoder = png::Decoder::new(File::open(path).unwrap_or_else(|e| fail(&e)));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().unwrap_or_else(|e| fail(&e));
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).unwrap_or_else(|e| fail(&e));
+
+    let channels = frame.color_type.samples();
+    let pixels = buffer[..frame.buffer_size()]
+        .chunks(channels)
+        .map(|p| {
+            let (luma, alpha) = match *p {
+                [l] => (l as f32, 255.0),
+                [l, a] => (l as f32, a as f32),
+                [r, g, b] => (luma(r, g, b), 255.0),
+                [r, g, b, a] => (luma(r, g, b), a as f32),
+                _ => unreachable!(),
+            };
+            // Transparent pixels are black, i.e. off
+            (luma * alpha / 255.0).round() as u8
+        })
+        .collect();
+
+    Gray {
+        width: frame.width as usize,
+        height: frame.height as usize,
+        pixels,
+    }
+}
+
+fn luma(r: u8, g: u8, b: u8) -> f32 {
+    0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32
+}
+
+/// Read a plain (`P1`) or raw (`P4`) portable bitmap, where 1 is black.
+fn read_pbm(path: &Path) -> Gray {
+    let fail = |msg: &str| -> ! { panic!("{}: {}", path.display(), msg) };
+
+    let data = fs::read(path).unwrap_or_else(|e| fail(&e.to_string()));
+    let mut at = 0;
+    let mut token = || {
+        loop {
+            match data.get(at) {
+                Some(b'#') => {
+                    while data.get(at).is_some_and(|&c| c != b'\n') {
+                        at += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => at += 1,
+                _ => break,
+            }
+        }
+        let start = at;
+        while data.get(at).is_some_and(|c| !c.is_ascii_whitespace()) {
+            at += 1;
+        }
+        String::from_utf8_lossy(&data[start..at]).into_owned()
+    };
+
+    let magic = token();
+    let mut number = || {
+        let word = token();
+        word.parse::<usize>()
+            .unwrap_or_else(|_| fail(&format!("invalid size `{word}`")))
+    };
+    let (width, height) = (number(), number());
+
+    let black: Vec<bool> = match magic.as_str() {
+        "P1" => data[at..]
+            .iter()
+            .filter(|c| !c.is_ascii_whitespace())
+            .map(|&c| c == b'1')
+            .collect(),
+        "P4" => {
+            // Exactly one whitespace character after the height
+            let bytes = data.get(at + 1..).unwrap_or_default();
+            let stride = width.div_ceil(8);
+            if width == 0 || bytes.len() < height * stride {
+                fail("not enough pixels");
+            }
+            let rows = bytes.chunks(stride).take(height);
+            rows.flat_map(|row| (0..width).map(move |x| row[x / 8] & (0x80 >> (x % 8)) != 0))
+                .collect()
+        }
+        _ => fail(&format!("not a PBM file (`{magic}`)")),
+    };
+    if black.len() < width * height {
+        fail("not enough pixels");
+    }
+
+    Gray {
+        width,
+        height,
+        pixels: black[..width * height]
+            .iter()
+            .map(|&black| if black { 0 } else { 255 })
+            .collect(),
+    }
+}
+
+/// Which pixels of `image` are on, row by row.
+fn to_bits(image: &Gray, options: &ImageOptions) -> Vec<bool> {
+    let threshold = options.threshold as f32;
+    let mut levels: Vec<f32> = image.pixels.iter().map(|&p| p as f32).collect();
+    let mut on = vec![false; levels.len()];
+
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let i = y * image.width + x;
+            on[i] = levels[i] >= threshold;
+
+            if options.dither {
+                // Spread the error onto the neighbours not done yet
+                let error = levels[i] - if on[i] { 255.0 } else { 0.0 };
+                let mut spread = |dx: isize, dy: usize, weight: f32| {
+                    let x = x as isize + dx;
+                    if (0..image.width as isize).contains(&x) && y + dy < image.height {
+                        levels[(y + dy) * image.width + x as usize] += error * weight / 16.0;
+                    }
+                };
+                spread(1, 0, 7.0);
+                spread(-1, 1, 3.0);
+                spread(0, 1, 5.0);
+                spread(1, 1, 1.0);
+            }
+        }
+    }
+
+    if options.invert {
+        on.iter_mut().for_each(|on| *on = !*on);
+    }
+    on
+}
+
+fn write_image(out: &mut String, path: &Path, image: &Gray, options: &ImageOptions) {
+    let name = const_name(path);
+    let file = path.file_name().unwrap().to_string_lossy();
+    let (width, height) = (image.width, image.height);
+
+    // Rows are padded to whole bytes, the leftmost pixel in the highest bit
+    let on = to_bits(image, options);
+    let bytes: Vec<u8> = on
+        .chunks(width)
+        .flat_map(|row| row.chunks(8))
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0, |acc, (i, &on)| acc | (on as u8) << (7 - i))
+        })
+        .collect();
+
+    writeln!(
+        out,
+        "/// Converted from `{IMAGES}/{file}`, {width}x{height} pixels."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "pub const {name}: ImageRaw<'static, BinaryColor> = ImageRaw::new(&{bytes:?}, {width});"
+    )
+    .unwrap();
+}
@@ -0,0 +1,119 @@
+//! Show the attitude of an MPU6050 on an SSD1306 mini oled display, as a
+//! cuboid on the left and an aircraft's attitude indicator on the right.
+//!
+//! The sensor is the aircraft, with its Y axis pointing to the nose and its X
+//! axis to the right wing.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+//! - An MPU6050 with SCL at µC pin B10 & SDA at µC pin B11
+
+#![no_main]
+#![no_std]
+
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle},
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use mpu6050::Mpu6050;
+use nalgebra::{UnitQuaternion, Vector3};
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    attitude::{AttitudeIndicator, Viewport},
+    camera::Camera,
+    i2c1, i2c2, oled,
+    scene::{Node, Scene},
+    shape3d::CUBOID,
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut mpu = Mpu6050::new(i2c2(
+        &clocks,
+        dp.I2C2,
+        gpiob.pb10,
+        gpiob.pb11,
+        &mut gpiob.crh,
+    ));
+    let mut delay = cp.SYST.delay(&clocks);
+    mpu.init(&mut delay).unwrap();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let line_style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+
+    let camera = Camera::default();
+    let mut scene = Scene::<_, 1>::new();
+    let cuboid = scene.add(Node::new(&CUBOID, line_style)).ok().unwrap();
+    let left = Rectangle::new(Point::zero(), Size::new(64, 64));
+
+    let indicator = AttitudeIndicator::new(Viewport::Circle(Circle::new(Point::new(65, 1), 62)));
+
+    loop {
+        let acc = mpu.get_acc().unwrap();
+        let angles = mpu.get_acc_angles().unwrap();
+
+        display.clear(BinaryColor::Off).unwrap();
+
+        scene.node_mut(cuboid).placement = pointing_to(&acc).into();
+        scene.draw_in(&mut display, left, &camera);
+
+        indicator.draw(&mut display, angles.y, angles.x).unwrap();
+
+        display.flush().unwrap();
+    }
+}
+
+/// The rotation turning the Y axis towards `direction`.
+fn pointing_to(direction: &Vector3<f32>) -> UnitQuaternion<f32> {
+    // `None` if `direction` points straight down
+    UnitQuaternion::rotation_between(&Vector3::y(), direction).unwrap_or_else(|| {
+        UnitQuaternion::from_axis_angle(&Vector3::x_axis(), core::f32::consts::PI)
+    })
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
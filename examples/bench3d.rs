@@ -0,0 +1,187 @@
+//! Compare the ways the 3D wireframe renderer can transform vertices.
+//!
+//! Draws a spinning cuboid & arrow, every few seconds switching between:
+//!
+//! - transforming both ends of every edge on its own, as `Shape3D` used to
+//! - transforming every vertex once with [`Backend::Float`], in a single
+//!   batched matrix multiply when built with `--features matrixmultiply`
+//! - transforming every vertex once with [`Backend::Fixed`]
+//!
+//! The cycles spent transforming & drawing the shapes (without flushing the
+//! display) are averaged over the frames drawn each way, shown on the display
+//! and printed via semihosting.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+
+#![no_main]
+#![no_std]
+
+use core::fmt::{Debug, Write};
+
+use cortex_m::peripheral::DWT;
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use cortex_m_semihosting::hprintln;
+use embedded_graphics::{
+    geometry::Point,
+    mono_font::{iso_8859_1, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, PrimitiveStyleBuilder},
+    text::{Baseline, Text},
+    Drawable,
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use nalgebra::{Matrix4, Point3, UnitQuaternion, Vector3};
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    camera::{Backend, Camera, Projector},
+    clip::clip_line,
+    i2c1, oled,
+    shape3d::{Placement, Shape3D, ARROW, CUBOID},
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+/// Frames drawn each way before switching.
+const FRAMES: u32 = 100;
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    PerEdge,
+    Batched(Backend),
+}
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let line_style = PrimitiveStyleBuilder::new()
+        .stroke_width(1)
+        .stroke_color(BinaryColor::On)
+        .build();
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&iso_8859_1::FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    let camera = Camera::new(Point3::new(0.0, 1.0, -2.5), Point3::origin());
+
+    let mut mode = Mode::PerEdge;
+    let mut angle = 0f32;
+    let mut frame = 0u32;
+    let mut total = 0u32;
+    let mut text = heapless::String::<32>::new();
+
+    loop {
+        display.clear(BinaryColor::Off).unwrap();
+
+        let start = DWT::cycle_count();
+
+        let projector = camera.projector(display.bounding_box());
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), angle);
+        let cuboid = Placement::from_rotation(rotation, 1.0).to_homogeneous();
+        let arrow = Placement::from_rotation(rotation.inverse(), 1.0).to_homogeneous();
+
+        match mode {
+            Mode::PerEdge => {
+                draw_per_edge(&mut display, &projector, line_style, &CUBOID, &cuboid);
+                draw_per_edge(&mut display, &projector, line_style, &ARROW, &arrow);
+            }
+            Mode::Batched(backend) => {
+                let projector = projector.with_backend(backend);
+                CUBOID.draw_projected(&mut display, &projector, line_style, &cuboid);
+                ARROW.draw_projected(&mut display, &projector, line_style, &arrow);
+            }
+        }
+
+        total = total.wrapping_add(DWT::cycle_count().wrapping_sub(start));
+
+        text.clear();
+        write!(text, "{:?} {}", mode, total / (frame % FRAMES + 1)).unwrap();
+        Text::with_baseline(&text, Point::new(1, 1), text_style, Baseline::Top)
+            .draw(&mut display)
+            .unwrap();
+
+        display.flush().unwrap();
+
+        frame += 1;
+        angle += 2f32.to_radians();
+
+        if frame.is_multiple_of(FRAMES) {
+            hprintln!("{:?}: {} cycles/frame", mode, total / FRAMES);
+            total = 0;
+            mode = match mode {
+                Mode::PerEdge => Mode::Batched(Backend::Float),
+                Mode::Batched(Backend::Float) => Mode::Batched(Backend::Fixed),
+                Mode::Batched(Backend::Fixed) => Mode::PerEdge,
+            };
+        }
+    }
+}
+
+/// Draw all edges of `shape`, transforming both ends of each edge separately.
+fn draw_per_edge<D, const V: usize, const E: usize, const F: usize>(
+    display: &mut D,
+    projector: &Projector,
+    style: PrimitiveStyle<BinaryColor>,
+    shape: &Shape3D<V, E, F>,
+    model: &Matrix4<f32>,
+) where
+    D: DrawTarget<Color = BinaryColor>,
+    D::Error: Debug,
+{
+    let clip_from_model = projector.clip_from_world() * model;
+    for &(f, t) in &shape.edges {
+        let a = clip_from_model * shape.vertices[f].to_homogeneous();
+        let b = clip_from_model * shape.vertices[t].to_homogeneous();
+        if let Some((a, b)) = clip_line(a, b) {
+            Line::new(projector.to_screen(a), projector.to_screen(b))
+                .into_styled(style)
+                .draw(display)
+                .unwrap();
+        }
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
@@ -0,0 +1,174 @@
+//! Fly a camera around a 3D wireframe on an SSD1306 mini oled display.
+//!
+//! The house is imported from `models/house.obj` at build time, and drawn
+//! with its hidden edges dashed and fading out with the distance from the
+//! camera.
+//!
+//! The left half of the screen shows a camera orbiting the shapes, zooming in &
+//! out and every few seconds switching between perspective and orthographic
+//! projection. The right half shows the same shapes from above.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+
+#![no_main]
+#![no_std]
+
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    geometry::Point,
+    mono_font::{iso_8859_1, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Line, PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+    Drawable,
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    camera::{Camera, Projection},
+    i2c1,
+    models::HOUSE,
+    oled,
+    shape3d::{DepthCue, ShapeStyle, ARROW},
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+/// Frames between switching the projection.
+const SWITCH_EVERY: u32 = 200;
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let line_style = PrimitiveStyleBuilder::new()
+        .stroke_width(1)
+        .stroke_color(BinaryColor::On)
+        .build();
+
+    let house_style = ShapeStyle::hidden_dashed(line_style).with_depth_cue(DepthCue::new(2.0, 4.0));
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&iso_8859_1::FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    let perspective = Projection::Perspective {
+        fovy: 60f32.to_radians(),
+    };
+    let orthographic = Projection::Orthographic { height: 2.0 };
+
+    let mut camera = Camera::new(Point3::new(0.0, 1.0, -2.5), Point3::origin());
+    let top = Camera {
+        position: Point3::new(0.0, 3.0, 0.0),
+        up: Vector3::z(),
+        projection: Projection::Orthographic { height: 3.0 },
+        ..Camera::default()
+    };
+
+    let upside_down = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), core::f32::consts::PI);
+
+    let left = Rectangle::new(Point::new(0, 0), Size::new(64, 64));
+    let right = Rectangle::new(Point::new(64, 0), Size::new(64, 64));
+
+    let mut frame = 0u32;
+
+    loop {
+        display.clear(BinaryColor::Off).unwrap();
+
+        for (viewport, camera) in [(left, &camera), (right, &top)] {
+            HOUSE.draw(
+                &mut display,
+                house_style,
+                camera,
+                UnitQuaternion::identity(),
+                Some(viewport),
+            );
+            ARROW.draw(
+                &mut display,
+                line_style,
+                camera,
+                upside_down,
+                Some(viewport),
+            );
+        }
+
+        Line::new(Point::new(64, 0), Point::new(64, 63))
+            .into_styled(line_style)
+            .draw(&mut display)
+            .unwrap();
+
+        let name = match camera.projection {
+            Projection::Perspective { .. } => "Persp.",
+            Projection::Orthographic { .. } => "Ortho.",
+        };
+        Text::with_baseline(name, Point::new(1, 1), text_style, Baseline::Top)
+            .draw(&mut display)
+            .unwrap();
+        Text::with_baseline("Top", Point::new(66, 1), text_style, Baseline::Top)
+            .draw(&mut display)
+            .unwrap();
+
+        display.flush().unwrap();
+
+        frame += 1;
+
+        camera.orbit(2f32.to_radians(), 0.0);
+        camera.zoom(if frame % SWITCH_EVERY < SWITCH_EVERY / 2 {
+            1.005
+        } else {
+            1.0 / 1.005
+        });
+
+        if frame.is_multiple_of(SWITCH_EVERY) {
+            camera.projection = match camera.projection {
+                Projection::Perspective { .. } => orthographic,
+                Projection::Orthographic { .. } => perspective,
+            };
+        }
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
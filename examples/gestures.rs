@@ -0,0 +1,127 @@
+//! Recognise tilt, flick and shake gestures with an MPU6050 and show them on
+//! an SSD1306 mini oled display.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+//! - An MPU6050 with SCL at µC pin B10 & SDA at µC pin B11
+
+#![no_main]
+#![no_std]
+
+use core::fmt::Write as _;
+use cortex_m::peripheral::DWT;
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    geometry::Point,
+    mono_font::{iso_8859_1, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+    Drawable,
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use heapless::String;
+use mpu6050::Mpu6050;
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    gesture::{Gesture, GestureConfig, GestureRecognizer},
+    i2c1, i2c2, oled,
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut mpu = Mpu6050::new(i2c2(
+        &clocks,
+        dp.I2C2,
+        gpiob.pb10,
+        gpiob.pb11,
+        &mut gpiob.crh,
+    ));
+    let mut delay = cp.SYST.delay(&clocks);
+    mpu.init(&mut delay).unwrap();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&iso_8859_1::FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    let mut gestures: GestureRecognizer = GestureRecognizer::new(GestureConfig::default());
+    let mut last: Option<Gesture> = None;
+    let mut count = 0u32;
+
+    let cycles_per_second = clocks.sysclk().raw() as f32;
+    let mut then = DWT::cycle_count();
+
+    loop {
+        let now = DWT::cycle_count();
+        let dt = now.wrapping_sub(then) as f32 / cycles_per_second;
+        then = now;
+
+        gestures.update(mpu.get_acc().unwrap(), mpu.get_gyro().unwrap(), dt);
+
+        while let Some(gesture) = gestures.poll() {
+            last = Some(gesture);
+            count += 1;
+        }
+
+        display.clear(BinaryColor::Off).unwrap();
+
+        {
+            let mut buffer: String<64> = String::new();
+            writeln!(buffer, "Tilt: {:?}", gestures.tilt()).unwrap();
+            writeln!(buffer, "Last: {:?}", last).unwrap();
+            writeln!(buffer, "Count: {}", count).unwrap();
+
+            Text::with_baseline(buffer.as_str(), Point::new(1, 1), text_style, Baseline::Top)
+                .draw(&mut display)
+                .unwrap();
+        }
+
+        display.flush().unwrap();
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
@@ -1,5 +1,9 @@
 //! Read data from an MPU6050 gyro/accel/temp sensor chip and display it on an
-//! SSD1306 mini oled display.
+//! SSD1306 mini oled display, counting steps along the way.
+//!
+//! The top right corner shows the frame rate and the time spent reading the
+//! sensor, transforming & rasterizing the 3D scene, drawing the text and
+//! flushing the display.
 //!
 //! ## µC Connections
 //!
@@ -10,10 +14,11 @@
 #![no_std]
 
 use core::fmt::Write as _;
+use cortex_m::peripheral::DWT;
 use cortex_m_rt::{entry, exception, ExceptionFrame};
 use embedded_graphics::{
     geometry::Point,
-    mono_font::{iso_8859_1, MonoTextStyleBuilder},
+    mono_font::{ascii, iso_8859_1, MonoTextStyleBuilder},
     pixelcolor::BinaryColor,
     prelude::*,
     primitives::PrimitiveStyleBuilder,
@@ -25,17 +30,21 @@ use hal::pac;
 use hal::prelude::*;
 use heapless::String;
 use mpu6050::Mpu6050;
-use nalgebra::Point3;
+use nalgebra::{UnitQuaternion, Vector3};
 use panic_semihosting as _;
-use ssd1306::{
-    prelude::*, rotation::DisplayRotation, size::DisplaySize128x64, I2CDisplayInterface, Ssd1306,
-};
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
 use stm32_experiments::{
-    i2c1, i2c2,
-    shape3d::{ARROW, CUBOID},
+    camera::Camera,
+    i2c1, i2c2, oled,
+    profile::{write_tenths, Corner, CycleCounted, Profiler},
+    scene::{Node, Scene},
+    shape3d::{ShadeStyle, ARROW, CUBOID},
+    step::{StepConfig, StepCounter},
 };
 use stm32f1xx_hal as hal;
 
+stm32_experiments::scratch_allocator!();
+
 #[entry]
 fn main() -> ! {
     let mut cp = cortex_m::Peripherals::take().unwrap();
@@ -67,19 +76,19 @@ fn main() -> ! {
     let mut delay = cp.SYST.delay(&clocks);
     mpu.init(&mut delay).unwrap();
 
-    let mut display = Ssd1306::new(
-        I2CDisplayInterface::new(i2c1(
+    // Only the parts of the screen that change, mostly the cuboid, are sent
+    let mut display = CycleCounted::new(oled::i2c(
+        i2c1(
             &clocks,
             dp.I2C1,
             gpiob.pb6,
             gpiob.pb7,
             &mut gpiob.crl,
             &mut afio.mapr,
-        )),
+        ),
         DisplaySize128x64,
         DisplayRotation::Rotate0,
-    )
-    .into_buffered_graphics_mode();
+    ));
     display.init().unwrap();
 
     let line_style = PrimitiveStyleBuilder::new()
@@ -92,29 +101,111 @@ fn main() -> ! {
         .text_color(BinaryColor::On)
         .build();
 
+    let overlay_style = MonoTextStyleBuilder::new()
+        .font(&ascii::FONT_4X6)
+        .text_color(BinaryColor::On)
+        .background_color(BinaryColor::Off)
+        .build();
+
+    let shade_style = ShadeStyle {
+        outline: Some(line_style),
+        ..ShadeStyle::default()
+    };
+
+    let camera = Camera::default();
+
+    // The arrow points the opposite way of the cuboid, wherever that is.
+    let mut scene = Scene::<_, 2>::new();
+    let cuboid = scene.add(Node::new(&CUBOID, shade_style)).ok().unwrap();
+    scene
+        .add(
+            Node::new(&ARROW, line_style)
+                .with_parent(cuboid)
+                .with_placement(upside_down()),
+        )
+        .ok()
+        .unwrap();
+
+    let mut steps = StepCounter::new(StepConfig::default());
+
+    let cycles_per_second = clocks.sysclk().raw();
+    let mut profiler = Profiler::new(
+        ["read", "transform", "raster", "text", "flush"],
+        cycles_per_second,
+    );
+    let mut then = DWT::cycle_count();
+
     loop {
-        display.clear(BinaryColor::Off).unwrap();
+        profiler.start_frame();
 
         let acc = mpu.get_acc().unwrap();
+        let temp = mpu.get_temp().unwrap();
 
-        CUBOID.draw(&mut display, line_style, &Point3::origin(), &acc.into());
-        ARROW.draw(&mut display, line_style, &Point3::origin(), &(-acc).into());
+        let now = DWT::cycle_count();
+        steps.update(
+            acc,
+            now.wrapping_sub(then) as f32 / cycles_per_second as f32,
+        );
+        then = now;
 
-        let temp = mpu.get_temp().unwrap();
+        profiler.end_phase();
+
+        // The scene is transformed & rasterized shape by shape, the display
+        // counts the cycles spent rasterizing
+        display.take_cycles();
+        display.clear(BinaryColor::Off).unwrap();
+
+        scene.node_mut(cuboid).placement = pointing_to(&acc).into();
+        scene.draw(&mut display, &camera);
+
+        profiler.end_interleaved(display.take_cycles());
 
         {
             let mut buffer: String<64> = String::new();
-            write!(buffer, "{:.1}°C", temp).unwrap();
+            // Rounded to tenths of a degree
+            write_tenths(&mut buffer, (temp * 10.0 + 0.5f32.copysign(temp)) as i32).unwrap();
+            buffer.push_str("°C").unwrap();
 
             Text::with_baseline(buffer.as_str(), Point::new(1, 1), text_style, Baseline::Top)
                 .draw(&mut display)
                 .unwrap();
         }
 
+        {
+            let mut buffer: String<64> = String::new();
+            let cadence = (steps.cadence() + 0.5) as u32;
+            write!(buffer, "{} steps {}/min", steps.steps(), cadence).unwrap();
+
+            Text::with_baseline(
+                buffer.as_str(),
+                Point::new(1, 63),
+                text_style,
+                Baseline::Bottom,
+            )
+            .draw(&mut display)
+            .unwrap();
+        }
+
+        profiler.draw_overlay(&mut display, Corner::TopRight, &overlay_style);
+
+        profiler.end_phase();
+
         display.flush().unwrap();
+
+        profiler.end_phase();
     }
 }
 
+/// The rotation turning the Y axis towards `direction`.
+fn pointing_to(direction: &Vector3<f32>) -> UnitQuaternion<f32> {
+    // `None` if `direction` points straight down
+    UnitQuaternion::rotation_between(&Vector3::y(), direction).unwrap_or_else(upside_down)
+}
+
+fn upside_down() -> UnitQuaternion<f32> {
+    UnitQuaternion::from_axis_angle(&Vector3::x_axis(), core::f32::consts::PI)
+}
+
 #[exception]
 unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
     panic!("{:#?}", ef);
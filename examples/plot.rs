@@ -0,0 +1,123 @@
+//! Plot the accelerometer & gyro readings of an MPU6050 on an SSD1306 mini
+//! oled display.
+//!
+//! The top half shows the acceleration in g, the bottom half the rotation in
+//! rad/s, each with a solid line for the X axis, a dashed one for Y and a
+//! dotted one for Z.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+//! - An MPU6050 with SCL at µC pin B10 & SDA at µC pin B11
+
+#![no_main]
+#![no_std]
+
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    mono_font::{ascii, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Text},
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use mpu6050::Mpu6050;
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    i2c1, i2c2, oled,
+    plot::{Plot, Scale},
+    raster::LinePattern,
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+/// The X, Y & Z axes.
+const PATTERNS: [LinePattern; 3] = [LinePattern::SOLID, LinePattern::DASHED, LinePattern::DOTTED];
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut mpu = Mpu6050::new(i2c2(
+        &clocks,
+        dp.I2C2,
+        gpiob.pb10,
+        gpiob.pb11,
+        &mut gpiob.crh,
+    ));
+    let mut delay = cp.SYST.delay(&clocks);
+    mpu.init(&mut delay).unwrap();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let text_style = MonoTextStyle::new(&ascii::FONT_4X6, BinaryColor::On);
+
+    // Lying still, one axis is at 1g
+    let mut acc: Plot<3, 100> = Plot::new(PATTERNS).with_scale(Scale::Fixed {
+        min: -2.0,
+        max: 2.0,
+    });
+    let mut gyro: Plot<3, 100> = Plot::new(PATTERNS);
+
+    let top = Rectangle::new(Point::new(0, 0), Size::new(128, 31));
+    let bottom = Rectangle::new(Point::new(0, 33), Size::new(128, 31));
+
+    loop {
+        let a = mpu.get_acc().unwrap();
+        acc.push([a.x, a.y, a.z]);
+        let g = mpu.get_gyro().unwrap();
+        gyro.push([g.x, g.y, g.z]);
+
+        display.clear(BinaryColor::Off).unwrap();
+
+        for (plot, area, name) in [(&acc, top, "acc g"), (&gyro, bottom, "gyro rad/s")] {
+            plot.draw(&mut display, area, BinaryColor::On, &text_style)
+                .unwrap();
+            let corner = Point::new(127, area.top_left.y + 5);
+            Text::with_alignment(name, corner, text_style, Alignment::Right)
+                .draw(&mut display)
+                .unwrap();
+        }
+
+        display.flush().unwrap();
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
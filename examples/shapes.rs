@@ -0,0 +1,182 @@
+//! Spin the generated 3D shapes on an SSD1306 mini oled display.
+//!
+//! Cycles through the Platonic solids, a sphere, cylinder, cone & torus, all
+//! drawn with hidden lines removed, with a labelled axis gizmo in the corner.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+
+#![no_main]
+#![no_std]
+
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    geometry::Point,
+    mono_font::{ascii, iso_8859_1, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+    Drawable,
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use nalgebra::{Point3, UnitQuaternion, Vector3};
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    camera::Camera,
+    i2c1,
+    label::{Label, LabelStyle},
+    oled,
+    shape3d::{Placement, Shape3D, ShapeStyle},
+    shapes::{
+        axes, cone, cone_size, cylinder, cylinder_size, torus, torus_size, uv_sphere,
+        uv_sphere_size, ShapeSize, CUBE, DODECAHEDRON, ICOSAHEDRON, OCTAHEDRON, TETRAHEDRON,
+    },
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+const SPHERE_SIZE: ShapeSize = uv_sphere_size(10, 6);
+const SPHERE: Shape3D<{ SPHERE_SIZE.vertices }, { SPHERE_SIZE.edges }, { SPHERE_SIZE.faces }> =
+    uv_sphere(10, 6, 1.0);
+
+const CYLINDER_SIZE: ShapeSize = cylinder_size(8);
+const CYLINDER: Shape3D<
+    { CYLINDER_SIZE.vertices },
+    { CYLINDER_SIZE.edges },
+    { CYLINDER_SIZE.faces },
+> = cylinder(8, 0.7, 1.4);
+
+const CONE_SIZE: ShapeSize = cone_size(8);
+const CONE: Shape3D<{ CONE_SIZE.vertices }, { CONE_SIZE.edges }, { CONE_SIZE.faces }> =
+    cone(8, 0.8, 1.4);
+
+const TORUS_SIZE: ShapeSize = torus_size(12, 6);
+const TORUS: Shape3D<{ TORUS_SIZE.vertices }, { TORUS_SIZE.edges }, { TORUS_SIZE.faces }> =
+    torus(12, 6, 0.7, 0.3);
+
+const NAMES: [&str; 9] = [
+    "Tetrahedron",
+    "Cube",
+    "Octahedron",
+    "Dodecahedron",
+    "Icosahedron",
+    "Sphere",
+    "Cylinder",
+    "Cone",
+    "Torus",
+];
+
+/// The tips of the gizmo's arrows, see [`axes`].
+const AXIS_LABELS: [Label; 3] = [
+    Label::at_vertex(1, "x"),
+    Label::at_vertex(2, "y"),
+    Label::at_vertex(3, "z"),
+];
+
+/// Frames each shape is shown for.
+const FRAMES: u32 = 150;
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let line_style = PrimitiveStyleBuilder::new()
+        .stroke_width(1)
+        .stroke_color(BinaryColor::On)
+        .build();
+    let style = ShapeStyle::hidden_line(line_style);
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&iso_8859_1::FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    let label_style = LabelStyle::new(
+        MonoTextStyleBuilder::new()
+            .font(&ascii::FONT_4X6)
+            .text_color(BinaryColor::On)
+            .build(),
+        BinaryColor::On,
+    );
+
+    let camera = Camera::new(Point3::new(0.0, 1.0, -2.5), Point3::origin());
+    let gizmo = axes(1.0);
+    let corner = Rectangle::new(Point::new(96, 32), Size::new(32, 32));
+    let mut frame = 0u32;
+
+    loop {
+        display.clear(BinaryColor::Off).unwrap();
+
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), frame as f32 * 0.03)
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), frame as f32 * 0.011);
+        let shape = (frame / FRAMES) as usize % NAMES.len();
+
+        let d = &mut display;
+        match shape {
+            0 => TETRAHEDRON.draw(d, style, &camera, rotation, None),
+            1 => CUBE.draw(d, style, &camera, rotation, None),
+            2 => OCTAHEDRON.draw(d, style, &camera, rotation, None),
+            3 => DODECAHEDRON.draw(d, style, &camera, rotation, None),
+            4 => ICOSAHEDRON.draw(d, style, &camera, rotation, None),
+            5 => SPHERE.draw(d, style, &camera, rotation, None),
+            6 => CYLINDER.draw(d, style, &camera, rotation, None),
+            7 => CONE.draw(d, style, &camera, rotation, None),
+            _ => TORUS.draw(d, style, &camera, rotation, None),
+        }
+
+        let projector = camera.projector(corner);
+        let model = Placement::from(rotation).to_homogeneous();
+        gizmo.draw_projected(d, &projector, line_style, &model);
+        gizmo.draw_labels(d, &projector, &AXIS_LABELS, &label_style, &model);
+
+        Text::with_baseline(NAMES[shape], Point::new(1, 1), text_style, Baseline::Top)
+            .draw(&mut display)
+            .unwrap();
+
+        display.flush().unwrap();
+
+        frame += 1;
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
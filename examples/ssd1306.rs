@@ -10,7 +10,6 @@
 
 use cortex_m_rt::{entry, exception, ExceptionFrame};
 use embedded_graphics::{
-    image::{Image, ImageRaw},
     mono_font::{iso_8859_1, MonoTextStyleBuilder},
     pixelcolor::BinaryColor,
     prelude::*,
@@ -23,9 +22,16 @@ use hal::{
     prelude::*,
 };
 use panic_semihosting as _;
-use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
+use ssd1306::prelude::*;
+use stm32_experiments::{
+    images::{RUST32, SPINNER},
+    oled,
+    sprite::{Animation, Sprite, SpriteSheet, Transparency},
+};
 use stm32f1xx_hal as hal;
 
+stm32_experiments::scratch_allocator!();
+
 #[entry]
 fn main() -> ! {
     let mut cp = cortex_m::Peripherals::take().unwrap();
@@ -68,9 +74,9 @@ fn main() -> ! {
         1000,
     );
 
-    let interface = I2CDisplayInterface::new(i2c);
-    let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate180)
-        .into_buffered_graphics_mode();
+    // Only the parts of the screen that change, mostly around the moving logo,
+    // are sent
+    let mut display = oled::i2c(i2c, DisplaySize128x64, DisplayRotation::Rotate180);
     display.init().unwrap();
 
     let yoffset = 32;
@@ -96,9 +102,17 @@ fn main() -> ! {
 
     let circle = Circle::new(Point::new(88, yoffset), 16).into_styled(style);
 
-    let raw: ImageRaw<BinaryColor> = ImageRaw::new(include_bytes!("../rust32.raw"), 32);
+    // Bounces around inside the outline, underneath everything else
+    let bounds = Rectangle::new(Point::new(2, 2), Size::new(123, 59));
+    let mut logo = Sprite::new(SpriteSheet::single(RUST32))
+        .with_position(Point::new(125 - 32, 61 - 32))
+        .with_velocity(Point::new(-1, -1));
 
-    let mut logo = Image::new(&raw, Point::new(0, 0));
+    // Spins in place on top of the text, showing each frame for 3 updates
+    let mut spinner = Sprite::new(SpriteSheet::new(SPINNER, Size::new_equal(12)))
+        .with_position(Point::new(104, 10))
+        .with_animation(Animation::new(0, 8, 3))
+        .with_transparency(Transparency::OnOnly);
 
     let text_style = MonoTextStyleBuilder::new()
         .font(&iso_8859_1::FONT_8X13)
@@ -112,41 +126,21 @@ fn main() -> ! {
         Baseline::Middle,
     );
 
-    let mut draw = |logo_x, logo_y| {
+    loop {
+        logo.update(bounds);
+        spinner.update(bounds);
+
         display.clear(BinaryColor::Off).unwrap();
 
-        logo.translate_mut(Point::new(logo_x, logo_y));
         logo.draw(&mut display).unwrap();
-        logo.translate_mut(Point::new(-logo_x, -logo_y));
-
         outline.draw(&mut display).unwrap();
         triangle.draw(&mut display).unwrap();
         rectangle.draw(&mut display).unwrap();
         circle.draw(&mut display).unwrap();
         hello_rust.draw(&mut display).unwrap();
+        spinner.draw(&mut display).unwrap();
 
         display.flush().unwrap();
-    };
-
-    loop {
-        const BX: i32 = 125 - 32;
-        const BY: i32 = 61 - 32;
-
-        for x in (2..=BX).rev() {
-            draw(x, BY);
-        }
-
-        for y in (2..=BY).rev() {
-            draw(2, y);
-        }
-
-        for x in 2..=BX {
-            draw(x, 2);
-        }
-
-        for y in 2..=BY {
-            draw(BX, y);
-        }
     }
 }
 
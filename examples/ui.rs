@@ -0,0 +1,227 @@
+//! A menu with a few screens of widgets on an SSD1306 mini oled display,
+//! controlled with a TTP229BSF keypad.
+//!
+//! The keypad's 16 keys are used like a phone's keypad:
+//!
+//! ```text
+//! 1 2 3 ↑
+//! 4 5 6 ↓
+//! 7 8 9 ←
+//! ⏎ 0 ✓ →
+//! ```
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+//! - A TTP229BSF (_not_ LSF) keypad with SCL at µC pin B13 & SDO at µC pin B14
+
+#![no_std]
+#![no_main]
+
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{mono_font::ascii, pixelcolor::BinaryColor, prelude::*};
+use hal::{pac, prelude::*, spi};
+use nb::block;
+use panic_semihosting as _;
+use ssd1306::prelude::*;
+use stm32_experiments::{
+    i2c1,
+    input::{from_ttp229, Key, Keypad},
+    oled,
+    ui::{
+        Caption, Checkbox, Form, Gauge, List, Menu, MessageBox, NumberSpinner, ProgressBar,
+        Response, Theme, Widget,
+    },
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+/// The key for each of the keypad's keys, row by row.
+const KEYS: [Option<Key>; 16] = [
+    Some(Key::Digit(1)),
+    Some(Key::Digit(2)),
+    Some(Key::Digit(3)),
+    Some(Key::Up),
+    Some(Key::Digit(4)),
+    Some(Key::Digit(5)),
+    Some(Key::Digit(6)),
+    Some(Key::Down),
+    Some(Key::Digit(7)),
+    Some(Key::Digit(8)),
+    Some(Key::Digit(9)),
+    Some(Key::Left),
+    Some(Key::Back),
+    Some(Key::Digit(0)),
+    Some(Key::Select),
+    Some(Key::Right),
+];
+
+const HELP: &[&str] = &[
+    "Up/down: move",
+    "Left/right: change",
+    "Digits: type, pick",
+    "OK: select, toggle",
+    "Back: to the menu",
+    "",
+    "Holding an arrow",
+    "key repeats it.",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Menu,
+    Settings,
+    Gauge,
+    Help,
+}
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let mut spi_keys = spi::Spi::spi2(
+        dp.SPI2,
+        (
+            gpiob.pb13.into_alternate_push_pull(&mut gpiob.crh),
+            gpiob.pb14.into_floating_input(&mut gpiob.crh),
+            gpiob.pb15.into_alternate_push_pull(&mut gpiob.crh),
+        ),
+        spi::Mode {
+            polarity: spi::Polarity::IdleHigh,
+            phase: spi::Phase::CaptureOnSecondTransition,
+        },
+        100.kHz(),
+        clocks,
+    )
+    .frame_size_16bit();
+
+    let mut delay = cp.SYST.delay(&clocks);
+
+    // Repeats after 0.4 s, 10 times a second
+    let mut keypad: Keypad = Keypad::new(KEYS).with_repeat(20, 5);
+
+    let theme = Theme::new(&ascii::FONT_6X10);
+    let bounds = display.bounding_box();
+
+    let mut screen = Screen::Menu;
+    let mut about: Option<MessageBox> = None;
+
+    let mut menu = Form::new((
+        Caption::title("stm32-experiments"),
+        Menu::new(&["1 Settings", "2 Gauge", "3 Help", "4 About"], 4),
+    ));
+    let mut settings = Form::new((
+        Caption::title("Settings"),
+        NumberSpinner::new("Contrast", 5, 0, 15),
+        ProgressBar { value: 5, max: 15 },
+        Checkbox::new("Invert", false),
+    ));
+    let mut gauge = Form::new((
+        Caption::title("Gauge"),
+        Gauge::new("Level", 0, 100).with_height(36),
+        NumberSpinner::new("Level", 0, 0, 100).with_step(5),
+    ));
+    let mut help = Form::new((Caption::title("Help"), List::new(HELP, 4)));
+
+    loop {
+        block!(spi_keys.send(0)).unwrap();
+        keypad.update(from_ttp229(block!(spi_keys.read()).unwrap()));
+
+        while let Some(key) = keypad.poll() {
+            if let Some(dialog) = &mut about {
+                if matches!(dialog.handle(key), Response::Activated | Response::Closed) {
+                    about = None;
+                }
+                continue;
+            }
+
+            let response = match screen {
+                Screen::Menu => menu.handle(key),
+                Screen::Settings => settings.handle(key),
+                Screen::Gauge => gauge.handle(key),
+                Screen::Help => help.handle(key),
+            };
+
+            match (screen, response) {
+                (Screen::Menu, Response::Activated) => match menu.widgets().1.selected {
+                    0 => screen = Screen::Settings,
+                    1 => screen = Screen::Gauge,
+                    2 => screen = Screen::Help,
+                    _ => {
+                        about = Some(MessageBox::new(
+                            "About",
+                            "Widgets on a\n128x64 display",
+                            &["OK"],
+                        ))
+                    }
+                },
+                (Screen::Settings, Response::Changed) => {
+                    let (_, contrast, bar, invert) = settings.widgets_mut();
+                    bar.value = contrast.value as u32;
+                    let oled = display.inner_mut();
+                    oled.set_brightness(Brightness::custom(2, contrast.value as u8 * 17))
+                        .unwrap();
+                    oled.set_invert(invert.checked).unwrap();
+                }
+                (Screen::Gauge, Response::Changed) => {
+                    let (_, dial, level) = gauge.widgets_mut();
+                    dial.value = level.value;
+                }
+                (_, Response::Ignored) if key == Key::Back => screen = Screen::Menu,
+                _ => {}
+            }
+        }
+
+        display.clear(BinaryColor::Off).unwrap();
+        match screen {
+            Screen::Menu => menu.draw(&mut display, bounds, &theme, true),
+            Screen::Settings => settings.draw(&mut display, bounds, &theme, true),
+            Screen::Gauge => gauge.draw(&mut display, bounds, &theme, true),
+            Screen::Help => help.draw(&mut display, bounds, &theme, true),
+        }
+        .unwrap();
+        if let Some(dialog) = &about {
+            dialog.draw(&mut display, bounds, &theme, true).unwrap();
+        }
+        display.flush().unwrap();
+
+        delay.delay_ms(20u16);
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
@@ -0,0 +1,152 @@
+//! Measure vibrations with an MPU6050 and show their spectrum on an SSD1306
+//! mini oled display.
+//!
+//! The accelerometer is sampled at a fixed rate until a block is complete, the
+//! block's spectrum is then computed & displayed before the next block is
+//! sampled.
+//!
+//! ## µC Connections
+//!
+//! - An SSD1306 with SCL at µC pin B6 & SDA at µC pin B7
+//! - An MPU6050 with SCL at µC pin B10 & SDA at µC pin B11
+
+#![no_main]
+#![no_std]
+
+use core::fmt::Write as _;
+use cortex_m_rt::{entry, exception, ExceptionFrame};
+use embedded_graphics::{
+    geometry::Point,
+    mono_font::{iso_8859_1, MonoTextStyleBuilder},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+    Drawable,
+};
+use hal::pac;
+#[allow(unused_imports)]
+use hal::prelude::*;
+use heapless::String;
+use mpu6050::Mpu6050;
+use nb::block;
+use panic_semihosting as _;
+use ssd1306::{rotation::DisplayRotation, size::DisplaySize128x64};
+use stm32_experiments::{
+    i2c1, i2c2, oled,
+    spectrum::{draw_spectrum, Spectrum},
+};
+use stm32f1xx_hal as hal;
+
+stm32_experiments::scratch_allocator!();
+
+/// Well within what the MPU6050 (1kHz accelerometer output rate) and the I2C
+/// bus can keep up with.
+const SAMPLE_RATE: u32 = 500;
+
+/// Acceleration (in g) at which a bar fills the graph.
+const FULL_SCALE: f32 = 0.25;
+
+#[entry]
+fn main() -> ! {
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(48.MHz())
+        .freeze(&mut flash.acr);
+
+    cp.DCB.enable_trace();
+    cp.DWT.enable_cycle_counter();
+
+    let mut afio = dp.AFIO.constrain();
+
+    let mut gpiob = dp.GPIOB.split();
+
+    let mut mpu = Mpu6050::new(i2c2(
+        &clocks,
+        dp.I2C2,
+        gpiob.pb10,
+        gpiob.pb11,
+        &mut gpiob.crh,
+    ));
+    let mut delay = cp.SYST.delay(&clocks);
+    mpu.init(&mut delay).unwrap();
+
+    let mut display = oled::i2c(
+        i2c1(
+            &clocks,
+            dp.I2C1,
+            gpiob.pb6,
+            gpiob.pb7,
+            &mut gpiob.crl,
+            &mut afio.mapr,
+        ),
+        DisplaySize128x64,
+        DisplayRotation::Rotate0,
+    );
+    display.init().unwrap();
+
+    let bar_style = PrimitiveStyleBuilder::new()
+        .stroke_width(1)
+        .stroke_color(BinaryColor::On)
+        .build();
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&iso_8859_1::FONT_6X10)
+        .text_color(BinaryColor::On)
+        .build();
+
+    let mut timer = dp.TIM2.counter_hz(&clocks);
+    timer.start(SAMPLE_RATE.Hz()).unwrap();
+
+    let mut spectrum: Spectrum<128> = Spectrum::new(SAMPLE_RATE as f32);
+
+    loop {
+        block!(timer.wait()).unwrap();
+
+        if !spectrum.push(mpu.get_acc().unwrap().norm()) {
+            continue;
+        }
+
+        display.clear(BinaryColor::Off).unwrap();
+
+        let magnitudes = spectrum.compute();
+        draw_spectrum(
+            &mut display,
+            Rectangle::new(Point::new(0, 12), Size::new(128, 52)),
+            magnitudes,
+            FULL_SCALE,
+            bar_style,
+        );
+
+        {
+            let mut buffer: String<64> = String::new();
+            match spectrum.peaks::<1>(0.01).first() {
+                Some(peak) => {
+                    write!(buffer, "{:5.1}Hz {:.3}g", peak.frequency, peak.magnitude).unwrap()
+                }
+                None => write!(buffer, "---").unwrap(),
+            }
+
+            Text::with_baseline(buffer.as_str(), Point::new(1, 1), text_style, Baseline::Top)
+                .draw(&mut display)
+                .unwrap();
+        }
+
+        display.flush().unwrap();
+
+        // Don't count the time spent drawing against the next block
+        timer.start(SAMPLE_RATE.Hz()).unwrap();
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
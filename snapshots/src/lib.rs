@@ -0,0 +1 @@
+//! Only has tests, see `tests/`.
@@ -0,0 +1,51 @@
+//! Clipping lines that cross behind the camera, where `w` is negative.
+
+use nalgebra::{Point3, Vector4};
+use stm32_experiments::{
+    camera::Camera,
+    clip::{clip_line, is_inside},
+};
+
+/// `p` in homogeneous clip space, as seen by the default camera at
+/// `(0, 0, -2)` looking at the origin.
+fn clip(p: Point3<f32>) -> Vector4<f32> {
+    let camera = Camera::default();
+    camera.projection(2.0) * camera.view() * p.to_homogeneous()
+}
+
+#[test]
+fn clipped_to_the_near_plane() {
+    let (front, behind) = (clip(Point3::origin()), clip(Point3::new(0.0, 0.0, -4.0)));
+    assert!(behind.w < 0.0);
+
+    let (a, b) = clip_line(front, behind).unwrap();
+    assert_eq!(a, front);
+    assert!(b.w > 0.0);
+    // On the near plane, z = -w
+    assert!((b.z + b.w).abs() < 1e-4 * b.w, "{:?}", b);
+}
+
+#[test]
+fn not_flipped_onto_the_screen() {
+    // To one side of the camera all along
+    let front = clip(Point3::new(0.5, 0.0, 0.0));
+    let behind = clip(Point3::new(0.5, 0.0, -4.0));
+    let side = (front.x / front.w).signum();
+    // Dividing by a negative `w` would put it on the other side
+    assert_eq!((behind.x / behind.w).signum(), -side);
+
+    let (a, b) = clip_line(front, behind).unwrap();
+    for p in [a, b] {
+        assert!(p.w > 0.0, "{:?}", p);
+        assert_eq!((p.x / p.w).signum(), side, "{:?}", p);
+    }
+    assert!(is_inside(&a));
+}
+
+#[test]
+fn fully_behind() {
+    let a = clip(Point3::new(-1.0, 0.5, -3.0));
+    let b = clip(Point3::new(1.0, -0.5, -5.0));
+    assert!(a.w < 0.0 && b.w < 0.0);
+    assert_eq!(clip_line(a, b), None);
+}
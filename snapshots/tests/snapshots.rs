@@ -0,0 +1,164 @@
+//! Compare frames drawn on the host with the golden images in
+//! `tests/snapshots`.
+//!
+//! Run with `cargo test` in this directory, and set `UPDATE_SNAPSHOTS=1` to
+//! accept changes.
+
+use embedded_graphics::{
+    image::GetPixel,
+    mono_font::{ascii, iso_8859_1, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle},
+};
+use embedded_hal::blocking::i2c;
+use mpu6050::Mpu6050;
+use nalgebra::Point3;
+use stm32_experiments::{
+    attitude::{AttitudeIndicator, Viewport},
+    camera::Camera,
+    framebuffer::{assert_snapshot, Framebuffer},
+    mpu::show_mpu_info,
+    plot::{Plot, Scale},
+    raster::LinePattern,
+    shape3d::{Placement, CUBOID},
+};
+
+fn snapshot(name: &str) -> String {
+    format!(
+        "{}/tests/snapshots/{}.png",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+#[test]
+fn cuboid() {
+    let mut frame = Framebuffer::new(Size::new(128, 64));
+    CUBOID.draw(
+        &mut frame,
+        PrimitiveStyle::with_stroke(BinaryColor::On, 1),
+        &Camera::default(),
+        Placement::face_towards(&Point3::origin(), &Point3::new(1.0, 1.0, 1.0)),
+        None,
+    );
+    assert_snapshot(&frame, snapshot("cuboid"));
+}
+
+#[test]
+fn attitude() {
+    let mut frame = Framebuffer::new(Size::new(128, 64));
+    let circle = AttitudeIndicator::new(Viewport::Circle(Circle::new(Point::new(1, 1), 62)));
+    circle.draw(&mut frame, 0.3, 0.2).unwrap();
+    let rectangle = AttitudeIndicator::new(Viewport::Rectangle(Rectangle::new(
+        Point::new(65, 1),
+        Size::new(62, 62),
+    )));
+    rectangle.draw(&mut frame, -0.5, -0.1).unwrap();
+    assert_snapshot(&frame, snapshot("attitude"));
+}
+
+#[test]
+fn plot() {
+    let mut frame = Framebuffer::new(Size::new(128, 64));
+    let mut plot: Plot<3, 100> =
+        Plot::new([LinePattern::SOLID, LinePattern::DASHED, LinePattern::DOTTED]).with_scale(
+            Scale::Fixed {
+                min: -2.0,
+                max: 2.0,
+            },
+        );
+    for i in 0..120 {
+        let t = i as f32 / 10.0;
+        plot.push([t.sin(), t.cos() * 1.5, 1.0]);
+    }
+    let text_style = MonoTextStyle::new(&ascii::FONT_4X6, BinaryColor::On);
+    let area = Rectangle::new(Point::zero(), Size::new(128, 64));
+    plot.draw(&mut frame, area, BinaryColor::On, &text_style)
+        .unwrap();
+    assert_snapshot(&frame, snapshot("plot"));
+}
+
+#[test]
+fn plot_ticks_smaller_than_an_ulp() {
+    // Adding steps of 0.02 to 1e6 rounds back to 1e6, this used to never
+    // finish
+    let mut frame = Framebuffer::new(Size::new(128, 64));
+    let mut plot: Plot<1, 10> = Plot::new([LinePattern::SOLID])
+        .with_scale(Scale::Fixed {
+            min: 1e6,
+            max: 1e6 + 0.1,
+        })
+        .with_ticks(0, 10);
+    plot.push([1e6]);
+    let text_style = MonoTextStyle::new(&ascii::FONT_4X6, BinaryColor::On);
+    let area = Rectangle::new(Point::zero(), Size::new(128, 64));
+    plot.draw(&mut frame, area, BinaryColor::On, &text_style)
+        .unwrap();
+    assert!(frame.count_on() > 0);
+}
+
+#[test]
+fn pbm_round_trip() {
+    let frame = Framebuffer::load(snapshot("attitude")).unwrap();
+    // Not a multiple of 8 wide, to pad the rows
+    let mut cropped = Framebuffer::new(Size::new(61, 64));
+    cropped
+        .draw_iter((0..61).flat_map(|x| {
+            let frame = &frame;
+            (0..64).map(move |y| {
+                let p = Point::new(x, y);
+                Pixel(p, frame.pixel(p).unwrap())
+            })
+        }))
+        .unwrap();
+    assert_eq!(Framebuffer::from_pbm(&cropped.to_pbm()).unwrap(), cropped);
+    assert_eq!(Framebuffer::from_png(&cropped.to_png()).unwrap(), cropped);
+}
+
+/// An MPU6050 on a bus that never fails, answering with fixed registers.
+struct FakeMpu {
+    registers: [u8; 0x80],
+}
+
+impl FakeMpu {
+    fn new() -> Self {
+        let mut registers = [0; 0x80];
+        // Accelerometer in 1/16384 g, temperature in 1/340 °C from 36.53 °C
+        // & gyroscope in 1/131 °/s, big endian
+        let values: [i16; 7] = [4096, -2048, 14336, -1000, 1310, -655, 262];
+        for (i, value) in values.iter().enumerate() {
+            registers[0x3B + 2 * i..][..2].copy_from_slice(&value.to_be_bytes());
+        }
+        Self { registers }
+    }
+}
+
+impl i2c::Write for FakeMpu {
+    type Error = ();
+
+    fn write(&mut self, _: u8, _: &[u8]) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+impl i2c::WriteRead for FakeMpu {
+    type Error = ();
+
+    fn write_read(&mut self, _: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), ()> {
+        let start = bytes[0] as usize;
+        buffer.copy_from_slice(&self.registers[start..start + buffer.len()]);
+        Ok(())
+    }
+}
+
+#[test]
+fn mpu_info() {
+    let mut frame = Framebuffer::new(Size::new(128, 64));
+    show_mpu_info(
+        &mut Mpu6050::new(FakeMpu::new()),
+        &mut frame,
+        MonoTextStyle::new(&iso_8859_1::FONT_6X10, BinaryColor::On),
+    );
+    assert_snapshot(&frame, snapshot("mpu_info"));
+}
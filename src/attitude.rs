@@ -0,0 +1,281 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Angle, Dimensions, Point},
+    pixelcolor::BinaryColor,
+    primitives::{
+        Arc, Circle, ContainsPoint as _, Line, PointsIter as _, Primitive as _, PrimitiveStyle,
+        PrimitiveStyleBuilder, Rectangle, StrokeAlignment, StyledDrawable as _, Triangle,
+    },
+    Drawable as _, Pixel,
+};
+// With std, `f32` has its own `sqrt` etc
+#[cfg(not(feature = "std"))]
+use nalgebra::ComplexField as _;
+
+use crate::raster::{dither, draw_line, LinePattern};
+
+/// The area an [`AttitudeIndicator`] is drawn into, nothing is drawn outside
+/// of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Viewport {
+    Rectangle(Rectangle),
+    Circle(Circle),
+}
+
+impl Viewport {
+    fn contains(&self, p: Point) -> bool {
+        match self {
+            Self::Rectangle(r) => r.contains(p),
+            Self::Circle(c) => c.contains(p),
+        }
+    }
+
+    fn center(&self) -> Point {
+        match self {
+            Self::Rectangle(r) => r.center(),
+            Self::Circle(c) => c.center(),
+        }
+    }
+}
+
+impl Dimensions for Viewport {
+    fn bounding_box(&self) -> Rectangle {
+        match self {
+            Self::Rectangle(r) => *r,
+            Self::Circle(c) => c.bounding_box(),
+        }
+    }
+}
+
+/// An aircraft's attitude indicator, showing which way is up.
+///
+/// The horizon is rotated by the roll and moves down as the nose pitches up,
+/// with a rung of the pitch ladder every few degrees, dashed below the
+/// horizon. Along the top, a pointer to the sky moves over a fixed scale of
+/// bank angles (10°, 20°, 30°, 45° & 60°), and in the middle a fixed aircraft
+/// symbol shows where the nose is.
+///
+/// ## Example
+///
+/// ```rs
+/// let indicator = AttitudeIndicator::new(Viewport::Circle(Circle::new(Point::new(64, 0), 64)));
+///
+/// loop {
+///     // With the sensor's Y axis pointing to the nose and X to the right wing
+///     let angles = mpu.get_acc_angles().unwrap();
+///     indicator.draw(&mut display, angles.y, angles.x).unwrap();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct AttitudeIndicator {
+    pub viewport: Viewport,
+    /// How far the horizon moves per degree of pitch.
+    pub pixels_per_degree: f32,
+    /// Degrees between the rungs of the pitch ladder, none for 0.
+    pub ladder_step: u32,
+    /// How bright the ground is drawn, dithered, see
+    /// [`dither`](crate::raster::dither), or not at all for `None`.
+    pub ground: Option<f32>,
+    pub bank_scale: bool,
+    /// Draw the edge of the viewport.
+    pub outline: bool,
+}
+
+impl AttitudeIndicator {
+    /// An indicator showing ±30° of pitch, with a rung every 10°.
+    pub fn new(viewport: Viewport) -> Self {
+        Self {
+            viewport,
+            pixels_per_degree: viewport.bounding_box().size.height as f32 / 60.0,
+            ladder_step: 10,
+            ground: Some(0.25),
+            bank_scale: true,
+            outline: true,
+        }
+    }
+
+    /// Draw the attitude for `roll` (positive with the right wing down) and
+    /// `pitch` (positive with the nose up), in radians.
+    pub fn draw<D>(&self, display: &mut D, roll: f32, pitch: f32) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let mut masked = Masked {
+            display,
+            viewport: self.viewport,
+        };
+        let area = self.viewport.bounding_box();
+        let center = self.viewport.center();
+        let (cx, cy) = (center.x as f32, center.y as f32);
+
+        // Along the horizon to the right, and up to the sky, on the screen
+        let (sin, cos) = roll.sin_cos();
+        let (along, up) = ((cos, -sin), (-sin, -cos));
+        let horizon = -pitch.to_degrees() * self.pixels_per_degree;
+        // The point `x` pixels to the right of the middle, `h` above the
+        // horizon
+        let at = |x: f32, h: f32| {
+            let h = horizon + h;
+            Point::new(
+                (cx + along.0 * x + up.0 * h).round() as i32,
+                (cy + along.1 * x + up.1 * h).round() as i32,
+            )
+        };
+
+        if let Some(intensity) = self.ground {
+            let below = |p: &Point| {
+                let (dx, dy) = ((p.x - center.x) as f32, (p.y - center.y) as f32);
+                dx * up.0 + dy * up.1 < horizon
+            };
+            masked.draw_iter(
+                area.points()
+                    .filter(below)
+                    .map(|p| Pixel(p, dither(intensity, p))),
+            )?;
+        }
+
+        let solid = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+        let reach = (area.size.width + area.size.height) as f32;
+        Line::new(at(-reach, 0.0), at(reach, 0.0)).draw_styled(&solid, &mut masked)?;
+
+        if self.ladder_step > 0 {
+            let width = area.size.width as f32;
+            let step = self.ladder_step as i32;
+            for degrees in (-90..=90).step_by(step as usize).filter(|&d| d != 0) {
+                let h = degrees as f32 * self.pixels_per_degree;
+                if (horizon + h).abs() > area.size.height as f32 {
+                    continue;
+                }
+                let half = if degrees % (2 * step) == 0 {
+                    width / 6.0
+                } else {
+                    width / 12.0
+                };
+                let pattern = if degrees < 0 {
+                    LinePattern::DASHED
+                } else {
+                    LinePattern::SOLID
+                };
+                let (a, b) = (at(-half, h), at(half, h));
+                draw_line(&mut masked, a, b, BinaryColor::On, pattern, (1.0, 1.0))?;
+            }
+        }
+
+        if self.bank_scale {
+            self.draw_bank_scale(&mut masked, center, roll)?;
+        }
+        self.draw_aircraft(&mut masked, center)?;
+
+        if self.outline {
+            let style = PrimitiveStyleBuilder::new()
+                .stroke_color(BinaryColor::On)
+                .stroke_width(1)
+                .stroke_alignment(StrokeAlignment::Inside)
+                .build();
+            match self.viewport {
+                Viewport::Rectangle(r) => r.draw_styled(&style, masked.display)?,
+                Viewport::Circle(c) => c.draw_styled(&style, masked.display)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_bank_scale<D>(&self, display: &mut D, center: Point, roll: f32) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let size = self.viewport.bounding_box().size;
+        let radius = (size.width.min(size.height) / 2).saturating_sub(5) as f32;
+        let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+        // At `angle` from straight up, clockwise, `r` from the center
+        let at = |angle: f32, r: f32| {
+            let (sin, cos) = angle.sin_cos();
+            center + Point::new((sin * r).round() as i32, -(cos * r).round() as i32)
+        };
+
+        Arc::with_center(
+            center,
+            2 * radius as u32 + 1,
+            Angle::from_degrees(-150.0),
+            Angle::from_degrees(120.0),
+        )
+        .draw_styled(&style, display)?;
+        for (degrees, length) in [(10, 2.0), (20, 2.0), (30, 4.0), (45, 2.0), (60, 4.0)] {
+            for angle in [degrees as f32, -degrees as f32] {
+                let angle = angle.to_radians();
+                Line::new(at(angle, radius), at(angle, radius + length))
+                    .draw_styled(&style, display)?;
+            }
+        }
+
+        // Points to the sky from inside the scale
+        let sky = -roll;
+        Triangle::new(
+            at(sky, radius - 1.0),
+            at(sky - 0.12, radius - 5.0),
+            at(sky + 0.12, radius - 5.0),
+        )
+        .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+        .draw(display)
+    }
+
+    fn draw_aircraft<D>(&self, display: &mut D, center: Point) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let width = self.viewport.bounding_box().size.width as i32;
+        let (inner, outer) = (width / 10, width / 4);
+        let wings = [
+            Line::new(
+                center + Point::new(-outer, 0),
+                center + Point::new(-inner, 0),
+            ),
+            Line::new(
+                center + Point::new(-inner, 0),
+                center + Point::new(-inner, 2),
+            ),
+            Line::new(center + Point::new(inner, 0), center + Point::new(outer, 0)),
+            Line::new(center + Point::new(inner, 0), center + Point::new(inner, 2)),
+            Line::new(center, center),
+        ];
+
+        // Outlined in black, to stand out on the ground & the ladder
+        let outline = PrimitiveStyle::with_stroke(BinaryColor::Off, 3);
+        let solid = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+        for style in [outline, solid] {
+            for wing in &wings {
+                wing.draw_styled(&style, display)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Only lets through the pixels within a viewport.
+struct Masked<'a, D> {
+    display: &'a mut D,
+    viewport: Viewport,
+}
+
+impl<D: DrawTarget> Dimensions for Masked<'_, D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.viewport.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for Masked<'_, D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let viewport = self.viewport;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(p, _)| viewport.contains(*p)),
+        )
+    }
+}
@@ -0,0 +1,240 @@
+use embedded_graphics::{geometry::Point, primitives::Rectangle};
+use nalgebra::{Matrix4, Perspective3, Point3, Unit, UnitQuaternion, Vector3, Vector4};
+
+use crate::fixed::{Fx, FxMatrix4};
+
+/// How a [`Camera`] projects the scene onto the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Perspective projection with a vertical field of view of `fovy` radians.
+    Perspective { fovy: f32 },
+    /// Orthographic projection showing `height` world units vertically.
+    Orthographic { height: f32 },
+}
+
+/// A camera looking from `position` at `target`.
+///
+/// The default camera is the one [`Shape3D::draw`](crate::shape3d::Shape3D::draw)
+/// used to hard-code: two units in front of the origin, looking at it with a
+/// 60° perspective projection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub position: Point3<f32>,
+    pub target: Point3<f32>,
+    pub up: Vector3<f32>,
+    pub projection: Projection,
+    /// Distance of the near clipping plane.
+    pub near: f32,
+    /// Distance of the far clipping plane.
+    pub far: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            position: Point3::new(0.0, 0.0, -2.0),
+            target: Point3::origin(),
+            up: Vector3::y(),
+            projection: Projection::Perspective {
+                fovy: 60f32.to_radians(),
+            },
+            near: 0.01,
+            far: 100.0,
+        }
+    }
+}
+
+impl Camera {
+    /// A camera at `position` looking at `target`, otherwise like the default
+    /// camera.
+    pub fn new(position: Point3<f32>, target: Point3<f32>) -> Self {
+        Self {
+            position,
+            target,
+            ..Self::default()
+        }
+    }
+
+    /// Precompute this camera's view & projection for `viewport`.
+    pub fn projector(&self, viewport: Rectangle) -> Projector {
+        Projector::new(self, viewport)
+    }
+
+    /// The view matrix, transforming world into camera coordinates.
+    pub fn view(&self) -> Matrix4<f32> {
+        Matrix4::look_at_rh(&self.position, &self.target, &self.up)
+    }
+
+    /// The projection matrix for a screen with the given aspect ratio (width
+    /// divided by height).
+    pub fn projection(&self, aspect: f32) -> Matrix4<f32> {
+        match self.projection {
+            Projection::Perspective { fovy } => {
+                Perspective3::new(aspect, fovy, self.near, self.far).to_homogeneous()
+            }
+            // The same matrix as `Orthographic3::new(-w, w, -h, h, near, far)`,
+            // whose `assert_ne!` would pull in formatting floats (~9 KiB)
+            Projection::Orthographic { height } => {
+                let (w, h) = (height * aspect / 2.0, height / 2.0);
+                let depth = self.far - self.near;
+                let mut m =
+                    Matrix4::new_nonuniform_scaling(&Vector3::new(1.0 / w, 1.0 / h, -2.0 / depth));
+                m[(2, 3)] = -(self.far + self.near) / depth;
+                m
+            }
+        }
+    }
+
+    /// The direction the camera looks in at `p`, both in camera coordinates.
+    ///
+    /// For a perspective projection that's the direction from the eye to `p`,
+    /// for an orthographic projection it's the same everywhere.
+    pub fn view_direction(&self, p: &Point3<f32>) -> Vector3<f32> {
+        match self.projection {
+            Projection::Perspective { .. } => p.coords,
+            Projection::Orthographic { .. } => -Vector3::z(),
+        }
+    }
+
+    /// Distance between the camera and its target.
+    pub fn distance(&self) -> f32 {
+        (self.position - self.target).norm()
+    }
+
+    /// Move the camera around its target, by `yaw` radians around the up
+    /// vector and by `pitch` radians upwards.
+    ///
+    /// The pitch is limited so the camera never looks exactly along the up
+    /// vector.
+    pub fn orbit(&mut self, yaw: f32, pitch: f32) {
+        const MARGIN: f32 = 0.01;
+
+        let offset = self.position - self.target;
+        let up = Unit::new_normalize(self.up);
+
+        let angle = up.angle(&offset);
+        let pitch = angle - (angle - pitch).clamp(MARGIN, core::f32::consts::PI - MARGIN);
+
+        let mut rotation = UnitQuaternion::from_axis_angle(&up, yaw);
+        if let Some(right) = Unit::try_new(offset.cross(&up), f32::EPSILON) {
+            rotation *= UnitQuaternion::from_axis_angle(&right, pitch);
+        }
+
+        self.position = self.target + rotation * offset;
+    }
+
+    /// Zoom in (`factor < 1.0`) or out (`factor > 1.0`), by moving the camera
+    /// towards or away from its target and scaling the visible height of an
+    /// orthographic projection.
+    pub fn zoom(&mut self, factor: f32) {
+        self.position = self.target + (self.position - self.target) * factor;
+        if let Projection::Orthographic { height } = &mut self.projection {
+            *height *= factor;
+        }
+    }
+}
+
+/// Which kind of arithmetic is used to transform vertices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// `f32`, precise but slow without an FPU.
+    #[default]
+    Float,
+    /// Q16.16 fixed-point, see [`Fx`]. Edges that need to be clipped still
+    /// fall back to `f32`.
+    Fixed,
+}
+
+/// A [`Camera`]'s view & projection for one viewport, computed once and then
+/// used for every shape drawn with that camera.
+#[derive(Clone, Copy, Debug)]
+pub struct Projector {
+    camera: Camera,
+    viewport: Rectangle,
+    backend: Backend,
+    view: Matrix4<f32>,
+    clip_from_world: Matrix4<f32>,
+    clip_from_world_fx: FxMatrix4,
+}
+
+impl Projector {
+    pub fn new(camera: &Camera, viewport: Rectangle) -> Self {
+        let view = camera.view();
+        let clip_from_world = camera.projection(aspect_ratio(&viewport)) * view;
+        Self {
+            camera: *camera,
+            viewport,
+            backend: Backend::default(),
+            view,
+            clip_from_world,
+            clip_from_world_fx: FxMatrix4::from_matrix(&clip_from_world),
+        }
+    }
+
+    pub fn with_backend(self, backend: Backend) -> Self {
+        Self { backend, ..self }
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn viewport(&self) -> Rectangle {
+        self.viewport
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// The camera's view matrix.
+    pub fn view(&self) -> &Matrix4<f32> {
+        &self.view
+    }
+
+    /// The combined projection & view matrix, transforming world into
+    /// homogeneous clip coordinates.
+    pub fn clip_from_world(&self) -> &Matrix4<f32> {
+        &self.clip_from_world
+    }
+
+    /// [`clip_from_world`](Self::clip_from_world) in fixed-point.
+    pub fn clip_from_world_fx(&self) -> &FxMatrix4 {
+        &self.clip_from_world_fx
+    }
+
+    /// Divide a point in clip coordinates by `w` and map it onto the viewport.
+    pub fn to_screen(&self, c: Vector4<f32>) -> Point {
+        let size = self.viewport.size;
+        let x = (c.x / c.w + 1.0) * size.width as f32 / 2.0;
+        let y = (c.y / c.w + 1.0) * size.height as f32 / 2.0;
+        self.viewport.top_left + Point::new(x as i32, y as i32)
+    }
+
+    /// How far a point in clip coordinates is in front of the camera, along
+    /// its view direction.
+    pub fn depth(&self, c: &Vector4<f32>) -> f32 {
+        match self.camera.projection {
+            // The perspective projection copies the depth into `w`
+            Projection::Perspective { .. } => c.w,
+            // ...while the orthographic one maps `near..far` onto `-1..1`
+            Projection::Orthographic { .. } => {
+                let (near, far) = (self.camera.near, self.camera.far);
+                (c.z / c.w * (far - near) + far + near) / 2.0
+            }
+        }
+    }
+
+    /// [`to_screen`](Self::to_screen) in fixed-point. `c.w` must be positive.
+    pub fn to_screen_fx(&self, c: [Fx; 4]) -> Point {
+        let half_width = Fx(self.viewport.size.width as i32 * (Fx::ONE.0 / 2));
+        let half_height = Fx(self.viewport.size.height as i32 * (Fx::ONE.0 / 2));
+        let x = (c[0] / c[3] + Fx::ONE) * half_width;
+        let y = (c[1] / c[3] + Fx::ONE) * half_height;
+        self.viewport.top_left + Point::new(x.to_int(), y.to_int())
+    }
+}
+
+fn aspect_ratio(viewport: &Rectangle) -> f32 {
+    viewport.size.width as f32 / viewport.size.height.max(1) as f32
+}
@@ -0,0 +1,89 @@
+use heapless::Vec;
+use nalgebra::Vector4;
+
+/// The six planes of the view frustum in homogeneous clip space, as the
+/// coefficients of `x`, `y`, `z` & `w`. A point is inside a plane if its dot
+/// product with the plane is not negative.
+///
+/// This follows the OpenGL convention used by [`nalgebra::Perspective3`] and
+/// [`nalgebra::Orthographic3`] where the visible volume is `-w <= x, y, z <= w`.
+const FRUSTUM: [Vector4<f32>; 6] = [
+    Vector4::new(1.0, 0.0, 0.0, 1.0),
+    Vector4::new(-1.0, 0.0, 0.0, 1.0),
+    Vector4::new(0.0, 1.0, 0.0, 1.0),
+    Vector4::new(0.0, -1.0, 0.0, 1.0),
+    // Near
+    Vector4::new(0.0, 0.0, 1.0, 1.0),
+    // Far
+    Vector4::new(0.0, 0.0, -1.0, 1.0),
+];
+
+/// Whether a point in homogeneous clip space is inside the view frustum.
+pub fn is_inside(p: &Vector4<f32>) -> bool {
+    FRUSTUM.iter().all(|plane| plane.dot(p) >= 0.0)
+}
+
+/// Clip the line from `a` to `b`, both in homogeneous clip space (i.e. after
+/// the projection but before the division by `w`), against the view frustum.
+///
+/// Returns the visible part of the line, or `None` if none of it is visible.
+/// Clipping before the division by `w` is what keeps points behind the camera,
+/// which have a negative `w`, from being flipped onto the screen.
+pub fn clip_line(a: Vector4<f32>, b: Vector4<f32>) -> Option<(Vector4<f32>, Vector4<f32>)> {
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+
+    for plane in &FRUSTUM {
+        let da = plane.dot(&a);
+        let db = plane.dot(&b);
+
+        if da < 0.0 && db < 0.0 {
+            return None;
+        } else if da < 0.0 {
+            t0 = t0.max(da / (da - db));
+        } else if db < 0.0 {
+            t1 = t1.min(da / (da - db));
+        }
+    }
+
+    if t0 > t1 {
+        return None;
+    }
+
+    Some((a.lerp(&b, t0), a.lerp(&b, t1)))
+}
+
+/// Clip the convex polygon `polygon`, in homogeneous clip space, against the
+/// view frustum.
+///
+/// Every plane can add at most one vertex to a convex polygon, so a triangle
+/// needs `N = 9`. Vertices that don't fit into `N` are dropped.
+pub fn clip_polygon<const N: usize>(polygon: &[Vector4<f32>]) -> Vec<Vector4<f32>, N> {
+    let mut output: Vec<Vector4<f32>, N> = polygon.iter().copied().take(N).collect();
+
+    for plane in &FRUSTUM {
+        if output.is_empty() {
+            break;
+        }
+
+        let input = output.clone();
+        output.clear();
+
+        let mut prev = input[input.len() - 1];
+        let mut d_prev = plane.dot(&prev);
+        for &here in &input {
+            let d_here = plane.dot(&here);
+
+            if (d_prev >= 0.0) != (d_here >= 0.0) {
+                let _ = output.push(prev.lerp(&here, d_prev / (d_prev - d_here)));
+            }
+            if d_here >= 0.0 {
+                let _ = output.push(here);
+            }
+
+            prev = here;
+            d_prev = d_here;
+        }
+    }
+
+    output
+}
@@ -0,0 +1,178 @@
+use display_interface::DisplayError;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+use ssd1306::{
+    command::AddrMode,
+    mode::BasicMode,
+    prelude::{DisplayRotation, WriteOnlyDataCommand},
+    size::{DisplaySize, NewZeroed},
+    Ssd1306,
+};
+
+/// An SSD1306 (or SH1106) framebuffer that only sends what changed since the
+/// last [`flush`](Self::flush).
+///
+/// See [`oled`](crate::oled) for setting one up for a particular module.
+///
+/// Unlike [`BufferedGraphicsMode`](ssd1306::mode::BufferedGraphicsMode),
+/// which sends everything within the bounding box of all pixels drawn, this
+/// keeps a copy of what's on the display and compares it with the new frame
+/// page by page (8 rows of pixels). Only the columns that differ are sent, so
+/// redrawing the whole frame every time, starting with a
+/// [`clear`](DrawTarget::clear), is cheap as long as most of it stays the
+/// same, e.g. for text that rarely changes.
+///
+/// That needs a second buffer, i.e. another 1024 bytes of RAM for a 128x64
+/// display.
+pub struct DiffDisplay<DI, SIZE: DisplaySize> {
+    display: Ssd1306<DI, SIZE, BasicMode>,
+    /// The frame being drawn.
+    frame: SIZE::Buffer,
+    /// The frame last sent to the display.
+    shown: SIZE::Buffer,
+    /// Whether `shown` can't be trusted, e.g. right after initialising the
+    /// display.
+    stale: bool,
+}
+
+impl<DI, SIZE> DiffDisplay<DI, SIZE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    pub fn new(display: Ssd1306<DI, SIZE, BasicMode>) -> Self {
+        Self {
+            display,
+            frame: NewZeroed::new_zeroed(),
+            shown: NewZeroed::new_zeroed(),
+            stale: true,
+        }
+    }
+
+    /// Initialise the display. The next flush sends the whole frame.
+    ///
+    /// Uses page addressing, the only mode an SH1106 has.
+    pub fn init(&mut self) -> Result<(), DisplayError> {
+        self.display.init_with_addr_mode(AddrMode::Page)?;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Send the whole frame on the next flush, e.g. after the display lost
+    /// its contents.
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Send the parts of the frame that changed since the last flush.
+    ///
+    /// Returns how many bytes of pixels were sent, at most 1024 for a 128x64
+    /// display.
+    pub fn flush(&mut self) -> Result<usize, DisplayError> {
+        let width = SIZE::WIDTH as usize;
+        let offset_x = match self.display.rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
+            // With the segments remapped, columns count from the other edge
+            DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
+                SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
+            }
+        };
+
+        let mut sent = 0;
+        let pages = self
+            .frame
+            .as_mut()
+            .chunks(width)
+            .zip(self.shown.as_mut().chunks_mut(width));
+        for (page, (frame, shown)) in pages.enumerate() {
+            let changed = |(a, b): (&u8, &u8)| self.stale || a != b;
+            let Some(first) = frame.iter().zip(&*shown).position(changed) else {
+                continue;
+            };
+            let last = width - 1 - frame.iter().zip(&*shown).rev().position(changed).unwrap();
+
+            self.display.set_row(page as u8 * 8 + SIZE::OFFSETY)?;
+            self.display.set_column(first as u8 + offset_x)?;
+            self.display.draw(&frame[first..=last])?;
+
+            shown[first..=last].copy_from_slice(&frame[first..=last]);
+            sent += last + 1 - first;
+        }
+
+        self.stale = false;
+        Ok(sent)
+    }
+
+    /// Turn the pixel at `x`, `y` on or off.
+    ///
+    /// Pixels outside the display are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, on: bool) {
+        let (x, y) = match self.display.rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (y, x),
+        };
+        if x >= SIZE::WIDTH as u32 || y >= SIZE::HEIGHT as u32 {
+            return;
+        }
+
+        let byte = &mut self.frame.as_mut()[(y / 8 * SIZE::WIDTH as u32 + x) as usize];
+        let bit = 1 << (y % 8);
+        if on {
+            *byte |= bit;
+        } else {
+            *byte &= !bit;
+        }
+    }
+
+    /// The display, e.g. to change its brightness.
+    pub fn inner_mut(&mut self) -> &mut Ssd1306<DI, SIZE, BasicMode> {
+        &mut self.display
+    }
+
+    pub fn into_inner(self) -> Ssd1306<DI, SIZE, BasicMode> {
+        self.display
+    }
+}
+
+impl<DI, SIZE> OriginDimensions for DiffDisplay<DI, SIZE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    fn size(&self) -> Size {
+        let (width, height) = self.display.dimensions();
+        Size::new(width.into(), height.into())
+    }
+}
+
+impl<DI, SIZE> DrawTarget for DiffDisplay<DI, SIZE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Color = BinaryColor;
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) {
+                self.set_pixel(x, y, color.is_on());
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.frame
+            .as_mut()
+            .fill(if color.is_on() { 0xff } else { 0 });
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use nalgebra::{Matrix4, Point3};
+
+/// A Q16.16 fixed-point number.
+///
+/// The Cortex-M3 has no FPU, so every `f32` operation is a call into the
+/// soft-float library. Fixed-point arithmetic mostly needs plain integer
+/// instructions (multiplying is a single 32x32 → 64 bit `SMULL`) at the cost
+/// of range (±32768) and precision (about 1.5e-5).
+///
+/// Dividing is the exception: it needs a 64 bit dividend, which the M3's
+/// 32 bit hardware divide can't take, so it's a call into the soft
+/// `__aeabi_ldivmod`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fx(pub i32);
+
+impl Fx {
+    pub const FRAC_BITS: u32 = 16;
+    pub const ZERO: Fx = Fx(0);
+    pub const ONE: Fx = Fx(1 << Self::FRAC_BITS);
+
+    pub const fn from_int(i: i32) -> Self {
+        Fx(i << Self::FRAC_BITS)
+    }
+
+    /// Convert from `f32`, saturating at the ends of the range.
+    pub fn from_f32(f: f32) -> Self {
+        Fx((f * Self::ONE.0 as f32) as i32)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / Self::ONE.0 as f32
+    }
+
+    /// The integer part, rounded towards negative infinity.
+    pub const fn to_int(self) -> i32 {
+        self.0 >> Self::FRAC_BITS
+    }
+
+    pub const fn abs(self) -> Self {
+        Fx(self.0.abs())
+    }
+}
+
+impl Add for Fx {
+    type Output = Fx;
+
+    fn add(self, rhs: Fx) -> Fx {
+        Fx(self.0.wrapping_add(rhs.0))
+    }
+}
+
+impl Sub for Fx {
+    type Output = Fx;
+
+    fn sub(self, rhs: Fx) -> Fx {
+        Fx(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl Neg for Fx {
+    type Output = Fx;
+
+    fn neg(self) -> Fx {
+        Fx(self.0.wrapping_neg())
+    }
+}
+
+impl Mul for Fx {
+    type Output = Fx;
+
+    fn mul(self, rhs: Fx) -> Fx {
+        Fx(((self.0 as i64 * rhs.0 as i64) >> Self::FRAC_BITS) as i32)
+    }
+}
+
+impl Div for Fx {
+    type Output = Fx;
+
+    /// Panics on division by zero.
+    ///
+    /// A soft 64 bit division, see [`Fx`].
+    fn div(self, rhs: Fx) -> Fx {
+        Fx((((self.0 as i64) << Self::FRAC_BITS) / rhs.0 as i64) as i32)
+    }
+}
+
+/// A 4x4 matrix of [`Fx`], stored row by row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FxMatrix4(pub [[Fx; 4]; 4]);
+
+impl FxMatrix4 {
+    pub fn from_matrix(m: &Matrix4<f32>) -> Self {
+        let mut rows = [[Fx::ZERO; 4]; 4];
+        for (r, row) in rows.iter_mut().enumerate() {
+            for (c, x) in row.iter_mut().enumerate() {
+                *x = Fx::from_f32(m[(r, c)]);
+            }
+        }
+        Self(rows)
+    }
+
+    /// Transform `p` (with `w = 1`) into homogeneous coordinates `[x, y, z, w]`.
+    pub fn transform_point(&self, p: &[Fx; 3]) -> [Fx; 4] {
+        let mut out = [Fx::ZERO; 4];
+        for (o, row) in out.iter_mut().zip(&self.0) {
+            *o = row[0] * p[0] + row[1] * p[1] + row[2] * p[2] + row[3];
+        }
+        out
+    }
+}
+
+impl Mul for &FxMatrix4 {
+    type Output = FxMatrix4;
+
+    fn mul(self, rhs: &FxMatrix4) -> FxMatrix4 {
+        let mut out = [[Fx::ZERO; 4]; 4];
+        for (r, row) in out.iter_mut().enumerate() {
+            for (c, x) in row.iter_mut().enumerate() {
+                *x = (0..4).fold(Fx::ZERO, |sum, k| sum + self.0[r][k] * rhs.0[k][c]);
+            }
+        }
+        FxMatrix4(out)
+    }
+}
+
+/// Convert a point to fixed-point.
+pub fn fx_point(p: &Point3<f32>) -> [Fx; 3] {
+    [Fx::from_f32(p.x), Fx::from_f32(p.y), Fx::from_f32(p.z)]
+}
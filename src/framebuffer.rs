@@ -0,0 +1,305 @@
+//! A framebuffer for drawing on the host instead of the display, e.g. to check
+//! what a frame looks like in a test.
+//!
+//! Images are black & white, with pixels that are on as white, the same way
+//! `build.rs` converts images for the display. PBM files are written in the
+//! binary format (`P4`), where 1 is black.
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
+    pixelcolor::BinaryColor,
+    Pixel,
+};
+use std::{
+    env, fs,
+    io::{self, BufRead as _, Read as _},
+    path::{Path, PathBuf},
+    string::ToString as _,
+    vec,
+    vec::Vec,
+};
+
+/// Set to write the frames as the new golden images instead of comparing
+/// them, see [`assert_snapshot`].
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// A [`DrawTarget`] keeping all pixels in memory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Framebuffer {
+    size: Size,
+    /// Row by row.
+    pixels: Vec<bool>,
+}
+
+impl Framebuffer {
+    /// A framebuffer with all pixels off, e.g. `Size::new(128, 64)` for an
+    /// SSD1306.
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            pixels: vec![false; (size.width * size.height) as usize],
+        }
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        let (x, y) = (u32::try_from(p.x).ok()?, u32::try_from(p.y).ok()?);
+        (x < self.size.width && y < self.size.height).then_some((y * self.size.width + x) as usize)
+    }
+
+    /// How many pixels are on.
+    pub fn count_on(&self) -> usize {
+        self.pixels.iter().filter(|&&on| on).count()
+    }
+
+    /// The pixels that differ from `other`, and an image of them: white where
+    /// both are on, red where only `self` is and green where only `other` is,
+    /// as an RGB PNG.
+    ///
+    /// `None` if both are the same. Frames of different sizes are compared
+    /// within the size they have in common, with all other pixels counted as
+    /// different.
+    pub fn diff(&self, other: &Self) -> Option<(usize, Vec<u8>)> {
+        let size = Size::new(
+            self.size.width.max(other.size.width),
+            self.size.height.max(other.size.height),
+        );
+
+        let mut different = 0;
+        let mut rgb = Vec::with_capacity((size.width * size.height * 3) as usize);
+        for y in 0..size.height as i32 {
+            for x in 0..size.width as i32 {
+                let p = Point::new(x, y);
+                let (a, b) = (self.pixel(p), other.pixel(p));
+                different += (a != b) as usize;
+                rgb.extend_from_slice(match (a.map(|c| c.is_on()), b.map(|c| c.is_on())) {
+                    (Some(true), Some(true)) => &[0xff, 0xff, 0xff],
+                    (Some(false), Some(false)) => &[0, 0, 0],
+                    (Some(true), _) => &[0xff, 0, 0],
+                    (_, Some(true)) => &[0, 0xff, 0],
+                    // Off or outside either of them
+                    _ => &[0x40, 0x40, 0x40],
+                });
+            }
+        }
+
+        (different > 0).then(|| (different, encode_png(size, png::ColorType::Rgb, &rgb)))
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let gray: Vec<u8> = self
+            .pixels
+            .iter()
+            .map(|&on| if on { 0xff } else { 0 })
+            .collect();
+        encode_png(self.size, png::ColorType::Grayscale, &gray)
+    }
+
+    /// Read a PNG, with pixels at least half as light as white on.
+    pub fn from_png(data: &[u8]) -> io::Result<Self> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().map_err(invalid)?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).map_err(invalid)?;
+
+        let channels = info.color_type.samples();
+        let pixels = buffer[..info.buffer_size()]
+            .chunks(channels)
+            .map(|p| {
+                // Ignores transparency
+                let light: u32 = p.iter().take(channels.min(3)).map(|&c| c as u32).sum();
+                light >= 0x80 * channels.min(3) as u32
+            })
+            .collect();
+        Ok(Self {
+            size: Size::new(info.width, info.height),
+            pixels,
+        })
+    }
+
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut pbm = std::format!("P4\n{} {}\n", self.size.width, self.size.height).into_bytes();
+        for row in self.pixels.chunks(self.size.width as usize) {
+            for byte in row.chunks(8) {
+                let black = byte
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |bits, (i, &on)| bits | (!on as u8) << (7 - i));
+                pbm.push(black);
+            }
+        }
+        pbm
+    }
+
+    /// Read a binary (`P4`) PBM, as written by [`to_pbm`](Self::to_pbm).
+    pub fn from_pbm(data: &[u8]) -> io::Result<Self> {
+        let mut reader = data;
+        let mut header = Vec::new();
+        // The magic number, width & height, skipping comments
+        while header.len() < 3 {
+            let mut line = std::string::String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid("incomplete PBM header"));
+            }
+            let line = line.split('#').next().unwrap_or_default();
+            header.extend(line.split_whitespace().map(|s| s.to_string()));
+        }
+        if header[0] != "P4" {
+            return Err(invalid("not a binary PBM file"));
+        }
+        let number = |s: &str| s.parse::<u32>().map_err(invalid);
+        let size = Size::new(number(&header[1])?, number(&header[2])?);
+
+        let stride = size.width.div_ceil(8) as usize;
+        let mut bits = vec![0; stride * size.height as usize];
+        reader.read_exact(&mut bits)?;
+        let pixels = bits
+            .chunks(stride)
+            .flat_map(|row| {
+                (0..size.width as usize).map(move |x| row[x / 8] & (0x80 >> (x % 8)) == 0)
+            })
+            .collect();
+        Ok(Self { size, pixels })
+    }
+
+    /// Read a `.png` or `.pbm` file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+        match extension(path) {
+            Some("png") => Self::from_png(&data),
+            Some("pbm") => Self::from_pbm(&data),
+            _ => Err(invalid("only .png & .pbm files are supported")),
+        }
+    }
+
+    /// Write a `.png` or `.pbm` file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let data = match extension(path) {
+            Some("png") => self.to_png(),
+            Some("pbm") => self.to_pbm(),
+            _ => return Err(invalid("only .png & .pbm files are supported")),
+        };
+        fs::write(path, data)
+    }
+}
+
+impl GetPixel for Framebuffer {
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<BinaryColor> {
+        self.index(p).map(|i| self.pixels[i].into())
+    }
+}
+
+impl OriginDimensions for Framebuffer {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Framebuffer {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(p, color) in pixels {
+            if let Some(i) = self.index(p) {
+                self.pixels[i] = color.is_on();
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels.fill(color.is_on());
+        Ok(())
+    }
+}
+
+/// Compare `frame` with the golden image at `path`, a `.png` or `.pbm` file,
+/// and panic if they differ.
+///
+/// On a mismatch, `frame` is written next to the golden image as
+/// `<name>.actual.png`, together with `<name>.diff.png` showing the
+/// differences, see [`Framebuffer::diff`]. If there's no golden image yet, or
+/// the environment variable [`UPDATE_SNAPSHOTS`] is set, `frame` is written
+/// as the golden image instead.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut frame = Framebuffer::new(Size::new(128, 64));
+/// CUBOID.draw(&mut frame, line_style, &camera, Placement::default(), None);
+/// assert_snapshot(&frame, "tests/snapshots/cuboid.png");
+/// ```
+pub fn assert_snapshot(frame: &Framebuffer, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let golden = match Framebuffer::load(path) {
+        Ok(golden) if env::var_os(UPDATE_SNAPSHOTS).is_none() => golden,
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            panic!("{}: {}", path.display(), e)
+        }
+        _ => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).unwrap();
+            }
+            frame.save(path).unwrap();
+            return;
+        }
+    };
+
+    let actual = sibling(path, "actual.png");
+    let diff_path = sibling(path, "diff.png");
+    match golden.diff(frame) {
+        None => {
+            // Clean up after an earlier mismatch
+            let _ = fs::remove_file(&actual);
+            let _ = fs::remove_file(&diff_path);
+        }
+        Some((different, diff)) => {
+            frame.save(&actual).unwrap();
+            fs::write(&diff_path, diff).unwrap();
+            panic!(
+                "{}: {} pixels differ, see {} & {} (red: only in the golden image, \
+                 green: only in the new frame), or set {} to accept the new frame",
+                path.display(),
+                different,
+                actual.display(),
+                diff_path.display(),
+                UPDATE_SNAPSHOTS,
+            );
+        }
+    }
+}
+
+/// `dir/name.png` → `dir/name.<suffix>`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(std::format!("{}.{}", stem, suffix))
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|e| e.to_str())
+}
+
+fn encode_png(size: Size, color: png::ColorType, data: &[u8]) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size.width, size.height);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(data).unwrap();
+    writer.finish().unwrap();
+    png
+}
+
+fn invalid(e: impl Into<std::boxed::Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
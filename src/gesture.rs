@@ -0,0 +1,271 @@
+use heapless::Deque;
+use nalgebra::Vector3;
+// With std, `f32` has its own `sqrt` etc
+#[cfg(not(feature = "std"))]
+use nalgebra::{ComplexField as _, RealField as _};
+
+/// A direction the board can be tilted or flicked in.
+///
+/// Left/right is a rotation around the board's Y axis, forward/back a rotation
+/// around its X axis (with the chip facing up).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Forward,
+    Back,
+}
+
+/// A gesture recognised by [`GestureRecognizer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gesture {
+    /// The board has been tilted past [`GestureConfig::tilt_enter`] and held
+    /// there.
+    Tilt(Direction),
+    /// The board has returned to level after a [`Gesture::Tilt`].
+    Level,
+    /// A short, fast rotation in the given direction.
+    Flick(Direction),
+    /// Several strong accelerations in quick succession.
+    Shake,
+}
+
+/// Thresholds for [`GestureRecognizer`].
+///
+/// Angles are in radians, rates in rad/s, accelerations in g and durations in
+/// seconds, matching what [`mpu6050::Mpu6050`] reports.
+#[derive(Clone, Copy, Debug)]
+pub struct GestureConfig {
+    /// Angle at which a tilt is recognised.
+    pub tilt_enter: f32,
+    /// Angle below which a tilt is released again. Keeping this below
+    /// `tilt_enter` keeps the tilt from flickering at the threshold.
+    pub tilt_exit: f32,
+    /// Maximum deviation of the acceleration magnitude from 1g for the
+    /// accelerometer to be trusted as a tilt sensor.
+    pub still_tolerance: f32,
+    /// Angular rate that starts a flick.
+    pub flick_rate: f32,
+    /// Longest a rotation may stay above `flick_rate` to count as a flick
+    /// rather than a (fast) tilt.
+    pub flick_max_duration: f32,
+    /// Deviation of the acceleration magnitude from 1g that counts as a shake
+    /// peak.
+    pub shake_threshold: f32,
+    /// Deviation the magnitude has to fall back below before the next shake
+    /// peak is counted.
+    pub shake_release: f32,
+    /// Number of peaks needed for a shake.
+    pub shake_count: u8,
+    /// Time window in which the `shake_count` peaks have to occur.
+    pub shake_window: f32,
+    /// Time after a flick or shake during which no further flicks or shakes
+    /// are recognised, so the return swing is not reported as a gesture.
+    pub cooldown: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tilt_enter: 25f32.to_radians(),
+            tilt_exit: 15f32.to_radians(),
+            still_tolerance: 0.3,
+            flick_rate: 4.0,
+            flick_max_duration: 0.25,
+            shake_threshold: 0.8,
+            shake_release: 0.3,
+            shake_count: 4,
+            shake_window: 1.0,
+            cooldown: 0.4,
+        }
+    }
+}
+
+/// Recognises tilt, flick and shake gestures from a stream of accelerometer &
+/// gyro readings.
+///
+/// Feed every reading into [`update`](Self::update) and take the recognised
+/// gestures out with [`poll`](Self::poll). At most `N` gestures are queued,
+/// further ones are dropped until the queue is polled.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut gestures: GestureRecognizer = GestureRecognizer::new(GestureConfig::default());
+///
+/// loop {
+///     gestures.update(mpu.get_acc().unwrap(), mpu.get_gyro().unwrap(), dt);
+///
+///     while let Some(gesture) = gestures.poll() {
+///         // ...
+///     }
+/// }
+/// ```
+pub struct GestureRecognizer<const N: usize = 8> {
+    config: GestureConfig,
+    events: Deque<Gesture, N>,
+    tilt: Option<Direction>,
+    flick: Option<(Direction, f32)>,
+    shake_high: bool,
+    shake_peaks: u8,
+    shake_elapsed: f32,
+    cooldown: f32,
+}
+
+impl<const N: usize> GestureRecognizer<N> {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            events: Deque::new(),
+            tilt: None,
+            flick: None,
+            shake_high: false,
+            shake_peaks: 0,
+            shake_elapsed: 0.0,
+            cooldown: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut GestureConfig {
+        &mut self.config
+    }
+
+    /// The direction the board is currently tilted in, if any.
+    pub fn tilt(&self) -> Option<Direction> {
+        self.tilt
+    }
+
+    /// Take the oldest recognised gesture out of the queue.
+    pub fn poll(&mut self) -> Option<Gesture> {
+        self.events.pop_front()
+    }
+
+    /// Process one reading, with `acc` in g, `gyro` in rad/s and `dt` the time
+    /// in seconds since the previous reading.
+    pub fn update(&mut self, acc: Vector3<f32>, gyro: Vector3<f32>, dt: f32) {
+        let deviation = (acc.norm() - 1.0).abs();
+
+        self.cooldown = (self.cooldown - dt).max(0.0);
+
+        self.update_shake(deviation, dt);
+        self.update_flick(gyro, dt);
+
+        // Under strong acceleration the accelerometer no longer points down,
+        // so only trust it for tilting when the board is (mostly) still.
+        if deviation <= self.config.still_tolerance {
+            self.update_tilt(acc);
+        }
+    }
+
+    fn update_tilt(&mut self, acc: Vector3<f32>) {
+        // Same as `Mpu6050::get_acc_angles`
+        let forward = acc.y.atan2((acc.x * acc.x + acc.z * acc.z).sqrt());
+        let right = (-acc.x).atan2((acc.y * acc.y + acc.z * acc.z).sqrt());
+
+        match self.tilt {
+            None => {
+                let tilt = if right.abs() >= forward.abs() {
+                    (right, Direction::Right, Direction::Left)
+                } else {
+                    (forward, Direction::Forward, Direction::Back)
+                };
+                if tilt.0.abs() >= self.config.tilt_enter {
+                    let direction = if tilt.0 > 0.0 { tilt.1 } else { tilt.2 };
+                    self.tilt = Some(direction);
+                    self.push(Gesture::Tilt(direction));
+                }
+            }
+            Some(direction) => {
+                let angle = match direction {
+                    Direction::Right => right,
+                    Direction::Left => -right,
+                    Direction::Forward => forward,
+                    Direction::Back => -forward,
+                };
+                if angle < self.config.tilt_exit {
+                    self.tilt = None;
+                    self.push(Gesture::Level);
+                }
+            }
+        }
+    }
+
+    fn update_flick(&mut self, gyro: Vector3<f32>, dt: f32) {
+        // Tilting right rotates the board negatively around its Y axis,
+        // tilting forward positively around its X axis.
+        let (rate, direction) = if gyro.y.abs() >= gyro.x.abs() {
+            let d = if gyro.y < 0.0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            };
+            (gyro.y.abs(), d)
+        } else {
+            let d = if gyro.x > 0.0 {
+                Direction::Forward
+            } else {
+                Direction::Back
+            };
+            (gyro.x.abs(), d)
+        };
+
+        match self.flick {
+            None => {
+                if rate >= self.config.flick_rate && self.cooldown == 0.0 {
+                    self.flick = Some((direction, 0.0));
+                }
+            }
+            Some((d, elapsed)) => {
+                let elapsed = elapsed + dt;
+                if rate < self.config.flick_rate {
+                    self.flick = None;
+                    if elapsed <= self.config.flick_max_duration {
+                        self.cooldown = self.config.cooldown;
+                        self.push(Gesture::Flick(d));
+                    }
+                } else {
+                    self.flick = Some((d, elapsed));
+                }
+            }
+        }
+    }
+
+    fn update_shake(&mut self, deviation: f32, dt: f32) {
+        if self.shake_peaks > 0 {
+            self.shake_elapsed += dt;
+            if self.shake_elapsed > self.config.shake_window {
+                self.shake_peaks = 0;
+            }
+        }
+
+        if self.shake_high {
+            self.shake_high = deviation > self.config.shake_release;
+        } else if deviation >= self.config.shake_threshold {
+            self.shake_high = true;
+
+            if self.shake_peaks == 0 {
+                self.shake_elapsed = 0.0;
+            }
+            self.shake_peaks += 1;
+
+            if self.shake_peaks >= self.config.shake_count {
+                self.shake_peaks = 0;
+                // A shake also spins the board around, don't report the tail
+                // end of that as a flick.
+                self.flick = None;
+                if self.cooldown == 0.0 {
+                    self.cooldown = self.config.cooldown;
+                    self.push(Gesture::Shake);
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, gesture: Gesture) {
+        let _ = self.events.push_back(gesture);
+    }
+}
@@ -0,0 +1,6 @@
+//! Images converted from the PNG & PBM files in `images/` at build time, see
+//! `build.rs`.
+
+use embedded_graphics::{image::ImageRaw, pixelcolor::BinaryColor};
+
+include!(concat!(env!("OUT_DIR"), "/images.rs"));
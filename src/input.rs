@@ -0,0 +1,148 @@
+use heapless::Deque;
+
+use crate::gesture::{Direction, Gesture};
+
+/// What the user asks a [`ui`](crate::ui) to do, independent of where it came
+/// from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+    /// `0` to `9`, e.g. to type a number or pick a menu item.
+    Digit(u8),
+}
+
+impl Key {
+    /// The key a gesture stands for, if any: tilting moves around, flicking
+    /// right selects and flicking left goes back.
+    pub fn from_gesture(gesture: Gesture) -> Option<Self> {
+        match gesture {
+            Gesture::Tilt(Direction::Forward) => Some(Self::Up),
+            Gesture::Tilt(Direction::Back) => Some(Self::Down),
+            Gesture::Tilt(Direction::Left) => Some(Self::Left),
+            Gesture::Tilt(Direction::Right) => Some(Self::Right),
+            Gesture::Flick(Direction::Right) => Some(Self::Select),
+            Gesture::Flick(Direction::Left) => Some(Self::Back),
+            _ => None,
+        }
+    }
+
+    /// Whether holding the key down repeats it.
+    fn repeats(self) -> bool {
+        matches!(self, Self::Up | Self::Down | Self::Left | Self::Right)
+    }
+}
+
+/// Turns the state of up to 16 buttons into [`Key`] presses.
+///
+/// Call [`update`](Self::update) regularly, e.g. once per frame, with which
+/// buttons are currently pressed and take the presses out with
+/// [`poll`](Self::poll). At most `N` presses are queued, further ones are
+/// dropped until the queue is polled.
+///
+/// Calling `update` every 20 ms or so is slow enough to not see mechanical
+/// buttons bounce.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut keypad: Keypad = Keypad::new(MAP).with_repeat(15, 3);
+///
+/// loop {
+///     block!(spi.send(0)).unwrap();
+///     keypad.update(from_ttp229(block!(spi.read()).unwrap()));
+///
+///     while let Some(key) = keypad.poll() {
+///         // ...
+///     }
+/// }
+/// ```
+pub struct Keypad<const N: usize = 8> {
+    /// The key for each button, by the button's bit.
+    map: [Option<Key>; 16],
+    /// Updates before a held key repeats, and between repeats.
+    repeat: Option<(u16, u16)>,
+    pressed: u16,
+    held: u16,
+    keys: Deque<Key, N>,
+}
+
+impl<const N: usize> Keypad<N> {
+    pub fn new(map: [Option<Key>; 16]) -> Self {
+        Self {
+            map,
+            repeat: None,
+            pressed: 0,
+            held: 0,
+            keys: Deque::new(),
+        }
+    }
+
+    /// Repeat the arrow keys while they're held down, first after `delay`
+    /// updates and then every `interval` updates.
+    pub fn with_repeat(self, delay: u16, interval: u16) -> Self {
+        Self {
+            repeat: Some((delay, interval.max(1))),
+            ..self
+        }
+    }
+
+    /// Process the state of the buttons, with bit `i` set while button `i` is
+    /// pressed.
+    pub fn update(&mut self, pressed: u16) {
+        let new = pressed & !self.pressed;
+        for (i, key) in self.map.iter().enumerate() {
+            if let Some(key) = key.filter(|_| new & (1 << i) != 0) {
+                let _ = self.keys.push_back(key);
+            }
+        }
+
+        if new != 0 || pressed != self.pressed {
+            self.held = 0;
+        } else if pressed != 0 {
+            self.held = self.held.saturating_add(1);
+            if let Some((delay, interval)) = self.repeat {
+                if self.held >= delay && (self.held - delay).is_multiple_of(interval) {
+                    self.repeat_held(pressed);
+                }
+            }
+        }
+        self.pressed = pressed;
+    }
+
+    /// Take the oldest key press out of the queue.
+    pub fn poll(&mut self) -> Option<Key> {
+        self.keys.pop_front()
+    }
+
+    fn repeat_held(&mut self, pressed: u16) {
+        for (i, key) in self.map.iter().enumerate() {
+            if let Some(key) = key.filter(|k| k.repeats() && pressed & (1 << i) != 0) {
+                let _ = self.keys.push_back(key);
+            }
+        }
+    }
+}
+
+/// Which keys of a TTP229BSF keypad are pressed, with bit `i` for key `i + 1`,
+/// from the 16 bits it sends via SPI, see the `ttp229_spi` example.
+pub fn from_ttp229(bits: u16) -> u16 {
+    // Sent with the last key first, and pressed keys pulled low
+    !bits.reverse_bits()
+}
+
+/// Which of `buttons` are pressed, with bit `i` for the `i`th one, e.g.
+/// `from_buttons([up.is_low(), down.is_low(), ok.is_low()])`.
+///
+/// Takes at most 16 buttons, one per bit.
+pub fn from_buttons<const B: usize>(buttons: [bool; B]) -> u16 {
+    const { assert!(B <= 16, "more buttons than bits") };
+    buttons
+        .into_iter()
+        .enumerate()
+        .fold(0, |bits, (i, pressed)| bits | (pressed as u16) << i)
+}
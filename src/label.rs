@@ -0,0 +1,160 @@
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    primitives::{Circle, Line, PrimitiveStyle, Rectangle, StyledDrawable as _},
+    text::{renderer::TextRenderer, Baseline},
+};
+use nalgebra::{Matrix4, Point3};
+
+use crate::{camera::Projector, clip::is_inside};
+
+/// A small 2D mark drawn where a [`Label`]'s anchor ends up on the screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Dot,
+    Cross,
+    Square,
+}
+
+/// Where a [`Label`] is anchored, in the coordinates of the shape it belongs
+/// to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Anchor {
+    /// The vertex with this index.
+    Vertex(usize),
+    Point(Point3<f32>),
+}
+
+/// A short text and/or a [`Marker`] attached to a 3D point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Label<'a> {
+    pub anchor: Anchor,
+    /// Nothing is drawn for an empty text, e.g. for labels with only a
+    /// marker.
+    pub text: &'a str,
+    pub marker: Option<Marker>,
+}
+
+impl<'a> Label<'a> {
+    pub const fn at_vertex(vertex: usize, text: &'a str) -> Self {
+        Self {
+            anchor: Anchor::Vertex(vertex),
+            text,
+            marker: None,
+        }
+    }
+
+    pub const fn at_point(point: Point3<f32>, text: &'a str) -> Self {
+        Self {
+            anchor: Anchor::Point(point),
+            text,
+            marker: None,
+        }
+    }
+
+    pub const fn with_marker(self, marker: Marker) -> Self {
+        Self {
+            marker: Some(marker),
+            ..self
+        }
+    }
+}
+
+/// How [`Label`]s are drawn.
+#[derive(Clone, Copy, Debug)]
+pub struct LabelStyle<S: TextRenderer> {
+    /// Any [`TextRenderer`], e.g. a
+    /// [`MonoTextStyle`](embedded_graphics::mono_font::MonoTextStyle).
+    pub text: S,
+    pub marker: PrimitiveStyle<S::Color>,
+    /// Width & height of the markers in pixels.
+    pub marker_size: u32,
+    /// Where the text starts relative to the anchor on the screen.
+    pub offset: Point,
+    pub baseline: Baseline,
+}
+
+impl<S: TextRenderer> LabelStyle<S> {
+    /// Text just above and to the right of the anchor, with 3 pixel markers
+    /// outlined in `color`.
+    pub fn new(text: S, color: S::Color) -> Self {
+        Self {
+            text,
+            marker: PrimitiveStyle::with_stroke(color, 1),
+            marker_size: 3,
+            offset: Point::new(3, -2),
+            baseline: Baseline::Bottom,
+        }
+    }
+}
+
+/// Draw `labels` anchored to the `vertices` of a shape transformed by `model`
+/// with `projector`, see [`Shape3D::draw_labels`](crate::shape3d::Shape3D::draw_labels).
+///
+/// Labels whose anchor is behind the camera or outside the viewport are
+/// skipped. Panics if a label is anchored to a vertex that doesn't exist.
+pub fn draw_labels<D, S>(
+    display: &mut D,
+    projector: &Projector,
+    vertices: &[Point3<f32>],
+    labels: &[Label],
+    style: &LabelStyle<S>,
+    model: &Matrix4<f32>,
+) where
+    D: DrawTarget<Color = S::Color>,
+    D::Error: Debug,
+    S: TextRenderer,
+{
+    let clip_from_model = projector.clip_from_world() * model;
+
+    for label in labels {
+        let anchor = match label.anchor {
+            Anchor::Vertex(i) => vertices[i],
+            Anchor::Point(p) => p,
+        };
+        let c = clip_from_model * anchor.to_homogeneous();
+        if !is_inside(&c) {
+            continue;
+        }
+        let p = projector.to_screen(c);
+
+        if let Some(marker) = label.marker {
+            draw_marker(display, marker, p, style);
+        }
+        if !label.text.is_empty() {
+            style
+                .text
+                .draw_string(label.text, p + style.offset, style.baseline, display)
+                .unwrap();
+        }
+    }
+}
+
+fn draw_marker<D, S>(display: &mut D, marker: Marker, center: Point, style: &LabelStyle<S>)
+where
+    D: DrawTarget<Color = S::Color>,
+    D::Error: Debug,
+    S: TextRenderer,
+{
+    let size = style.marker_size;
+    match marker {
+        Marker::Dot => Circle::with_center(center, size)
+            .draw_styled(&style.marker, display)
+            .unwrap(),
+        Marker::Cross => {
+            let r = size as i32 / 2;
+            for (from, to) in [
+                (Point::new(-r, -r), Point::new(r, r)),
+                (Point::new(-r, r), Point::new(r, -r)),
+            ] {
+                Line::new(center + from, center + to)
+                    .draw_styled(&style.marker, display)
+                    .unwrap();
+            }
+        }
+        Marker::Square => Rectangle::with_center(center, Size::new_equal(size))
+            .draw_styled(&style.marker, display)
+            .unwrap(),
+    }
+}
@@ -1,6 +1,33 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod attitude;
+pub mod camera;
+pub mod clip;
+pub mod diff_display;
+pub mod fixed;
+#[cfg(feature = "std")]
+pub mod framebuffer;
+pub mod gesture;
+pub mod images;
+pub mod input;
+pub mod label;
+pub mod models;
+pub mod oled;
+pub mod plot;
+pub mod profile;
+pub mod raster;
+pub mod scene;
+#[cfg(feature = "matrixmultiply")]
+pub mod scratch;
 pub mod shape3d;
+pub mod shapes;
+pub mod spectrum;
+pub mod sprite;
+pub mod step;
+pub mod ui;
 pub mod mpu;
 
 use hal::{
@@ -13,6 +40,22 @@ use hal::{
 };
 use stm32f1xx_hal as hal;
 
+/// Declare a [`ScratchAllocator`](scratch::ScratchAllocator) as the global
+/// allocator when built with the `matrixmultiply` feature, which needs one for
+/// its packing buffers, and nothing otherwise.
+///
+/// The feature is checked in the crate calling this, i.e. meant for the
+/// examples & binaries of this package.
+#[macro_export]
+macro_rules! scratch_allocator {
+    () => {
+        #[cfg(feature = "matrixmultiply")]
+        #[global_allocator]
+        static ALLOCATOR: $crate::scratch::ScratchAllocator =
+            $crate::scratch::ScratchAllocator::new();
+    };
+}
+
 const START_TIMEOUT_US: u32 = 100000;
 const START_RETRIES: u8 = 10;
 const ADDR_TIMEOUT_US: u32 = 10000;
@@ -0,0 +1,8 @@
+//! 3D models imported from the Wavefront OBJ files in `models/` at build time,
+//! see `build.rs`.
+
+use nalgebra::point;
+
+use crate::shape3d::Shape3D;
+
+include!(concat!(env!("OUT_DIR"), "/models.rs"));
@@ -0,0 +1,158 @@
+//! Setting up the mini oled displays we have, SSD1306s with 128x64 or 128x32
+//! pixels and SH1106s with 128x64, on I2C or SPI, all as a [`DiffDisplay`].
+//!
+//! The size picks the controller & panel, the function the interface:
+//!
+//! | Module         | Size                                           |
+//! |----------------|------------------------------------------------|
+//! | SSD1306 128x64 | [`DisplaySize128x64`](ssd1306::size::DisplaySize128x64) |
+//! | SSD1306 128x32 | [`DisplaySize128x32`](ssd1306::size::DisplaySize128x32) |
+//! | SH1106 128x64  | [`Sh1106Size128x64`]                           |
+//!
+//! ## Example
+//!
+//! ```rs
+//! let mut display = oled::i2c(
+//!     i2c1(&clocks, dp.I2C1, gpiob.pb6, gpiob.pb7, &mut gpiob.crl, &mut afio.mapr),
+//!     Sh1106Size128x64,
+//!     DisplayRotation::Rotate0,
+//! );
+//! display.init().unwrap();
+//! ```
+//!
+//! Or an SSD1306 128x32 on SPI1, with D/C at µC pin A3 & CS at µC pin A4:
+//!
+//! ```rs
+//! let spi = Spi::spi1(
+//!     dp.SPI1,
+//!     (
+//!         gpioa.pa5.into_alternate_push_pull(&mut gpioa.crl),
+//!         NoMiso,
+//!         gpioa.pa7.into_alternate_push_pull(&mut gpioa.crl),
+//!     ),
+//!     &mut afio.mapr,
+//!     spi::Mode {
+//!         polarity: spi::Polarity::IdleLow,
+//!         phase: spi::Phase::CaptureOnFirstTransition,
+//!     },
+//!     8.MHz(),
+//!     clocks,
+//! );
+//! let dc = gpioa.pa3.into_push_pull_output(&mut gpioa.crl);
+//! let cs = gpioa.pa4.into_push_pull_output(&mut gpioa.crl);
+//! let mut display = oled::spi(spi, dc, cs, DisplaySize128x32, DisplayRotation::Rotate0);
+//! ```
+
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{
+    blocking::{i2c, spi},
+    digital::v2::OutputPin,
+};
+use ssd1306::{
+    command::Command,
+    prelude::{DisplayRotation, I2CInterface, SPIInterface},
+    size::DisplaySize,
+    I2CDisplayInterface, Ssd1306,
+};
+
+use crate::diff_display::DiffDisplay;
+
+/// The 128x64 panel of an SH1106, in the middle of the controller's 132
+/// columns.
+///
+/// The SH1106 shares most of its commands with an SSD1306 in page addressing
+/// mode, which [`DiffDisplay`] uses, and [`Interface`] translates or drops the
+/// rest.
+#[derive(Debug, Copy, Clone)]
+pub struct Sh1106Size128x64;
+
+impl DisplaySize for Sh1106Size128x64 {
+    const WIDTH: u8 = 128;
+    const HEIGHT: u8 = 64;
+    const DRIVER_COLS: u8 = 132;
+    const OFFSETX: u8 = 2;
+    type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
+
+    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
+        Command::ComPinConfig(true, false).send(iface)
+    }
+}
+
+/// The interface to a module, sending an SH1106 only the commands it has.
+///
+/// An SH1106 has no idea what the SSD1306's two byte commands without a
+/// counterpart are, e.g. enabling the charge pump with `8D 14`, and takes
+/// their second byte for a command of its own, `14` setting the column. So
+/// the charge pump is switched with the DC-DC converter's `AD 8B` instead,
+/// and addressing modes, address ranges & scrolling aren't sent at all.
+///
+/// SH1106s are told apart by their 132 columns, SSD1306s have 128.
+pub struct Interface<DI> {
+    interface: DI,
+    sh1106: bool,
+}
+
+impl<DI> Interface<DI> {
+    fn new<SIZE: DisplaySize>(interface: DI) -> Self {
+        Self {
+            interface,
+            sh1106: SIZE::DRIVER_COLS == Sh1106Size128x64::DRIVER_COLS,
+        }
+    }
+}
+
+impl<DI: WriteOnlyDataCommand> WriteOnlyDataCommand for Interface<DI> {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            DataFormat::U8(&[0x8D, pump]) if self.sh1106 => {
+                let on = pump & 0x04 != 0;
+                self.interface
+                    .send_commands(DataFormat::U8(&[0xAD, 0x8A | on as u8]))
+            }
+            // Addressing mode, column & page range, scrolling
+            DataFormat::U8(&[0x20..=0x2F | 0xA3, ..]) if self.sh1106 => Ok(()),
+            cmd => self.interface.send_commands(cmd),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_data(buf)
+    }
+}
+
+/// A module on I2C, at the usual address 0x3C.
+pub fn i2c<I2C, SIZE>(
+    i2c: I2C,
+    size: SIZE,
+    rotation: DisplayRotation,
+) -> DiffDisplay<Interface<I2CInterface<I2C>>, SIZE>
+where
+    I2C: i2c::Write,
+    SIZE: DisplaySize,
+{
+    let interface = Interface::new::<SIZE>(I2CDisplayInterface::new(i2c));
+    DiffDisplay::new(Ssd1306::new(interface, size, rotation))
+}
+
+/// A module on SPI, with `dc` telling commands (low) from data (high) and
+/// `cs` selecting it (low).
+///
+/// Modules with a reset pin should be reset before
+/// [`init`](DiffDisplay::init), e.g. with
+/// `display.inner_mut().reset(&mut rst, &mut delay)`.
+pub fn spi<SPI, DC, CS, SIZE>(
+    spi: SPI,
+    dc: DC,
+    cs: CS,
+    size: SIZE,
+    rotation: DisplayRotation,
+) -> DiffDisplay<Interface<SPIInterface<SPI, DC, CS>>, SIZE>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    CS: OutputPin,
+    SIZE: DisplaySize,
+{
+    let interface = Interface::new::<SIZE>(SPIInterface::new(spi, dc, cs));
+    DiffDisplay::new(Ssd1306::new(interface, size, rotation))
+}
@@ -0,0 +1,267 @@
+use core::fmt;
+use embedded_graphics::{
+    draw_target::{DrawTarget, DrawTargetExt as _},
+    geometry::Point,
+    primitives::{Line, PointsIter as _, Rectangle},
+    text::{renderer::TextRenderer, Baseline},
+    Pixel,
+};
+use heapless::{HistoryBuffer, String};
+// With std, `f32` has its own `sqrt` etc
+#[cfg(not(feature = "std"))]
+use nalgebra::ComplexField as _;
+
+use crate::raster::LinePattern;
+
+/// How a [`Plot`] maps values to its height.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    /// From the smallest to the largest value in the history.
+    Auto,
+    /// From `min` to `max`, cutting off values outside.
+    ///
+    /// Ranges that are empty or upside down are drawn from 1 below to 1 above
+    /// their middle instead.
+    Fixed { min: f32, max: f32 },
+}
+
+/// A scrolling plot of the last `N` values of `C` channels, e.g. the X, Y & Z
+/// axes of an accelerometer.
+///
+/// Every [`push`](Self::push) adds one value per channel, dropping the oldest
+/// ones once the history is full. The newest values are drawn at the right
+/// edge, one trace per channel, each with its own [`LinePattern`] so they can
+/// be told apart on a monochrome display.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut plot: Plot<3, 100> =
+///     Plot::new([LinePattern::SOLID, LinePattern::DASHED, LinePattern::DOTTED]);
+///
+/// loop {
+///     let acc = mpu.get_acc().unwrap();
+///     plot.push([acc.x, acc.y, acc.z]);
+///
+///     plot.draw(&mut display, area, BinaryColor::On, &text_style).unwrap();
+/// }
+/// ```
+pub struct Plot<const C: usize, const N: usize> {
+    channels: [HistoryBuffer<f32, N>; C],
+    patterns: [LinePattern; C],
+    pub scale: Scale,
+    /// Ticks along the bottom every that many values, or none for 0.
+    pub x_ticks: u32,
+    /// How many ticks along the left edge at most, at round values.
+    pub y_ticks: u32,
+    /// Values pushed so far, so the X ticks scroll along with the values.
+    pushed: u32,
+}
+
+impl<const C: usize, const N: usize> Plot<C, N> {
+    /// An empty, automatically scaled plot, drawing channel `i` with
+    /// `patterns[i]`.
+    pub fn new(patterns: [LinePattern; C]) -> Self {
+        Self {
+            channels: core::array::from_fn(|_| HistoryBuffer::new()),
+            patterns,
+            scale: Scale::Auto,
+            x_ticks: 10,
+            y_ticks: 4,
+            pushed: 0,
+        }
+    }
+
+    pub fn with_scale(self, scale: Scale) -> Self {
+        Self { scale, ..self }
+    }
+
+    pub fn with_ticks(self, x_ticks: u32, y_ticks: u32) -> Self {
+        Self {
+            x_ticks,
+            y_ticks,
+            ..self
+        }
+    }
+
+    /// Add the next value of every channel.
+    pub fn push(&mut self, values: [f32; C]) {
+        for (channel, value) in self.channels.iter_mut().zip(values) {
+            channel.write(value);
+        }
+        self.pushed = self.pushed.wrapping_add(1);
+    }
+
+    pub fn clear(&mut self) {
+        for channel in &mut self.channels {
+            channel.clear();
+        }
+    }
+
+    /// The history of channel `channel`, oldest value first.
+    pub fn values(&self, channel: usize) -> impl Iterator<Item = &f32> {
+        self.channels[channel].oldest_ordered()
+    }
+
+    /// The smallest and the largest value in the history, if there is any.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.channels
+            .iter()
+            .flat_map(|channel| channel.iter())
+            .fold(None, |range, &v| match range {
+                None => Some((v, v)),
+                Some((min, max)) => Some((v.min(min), v.max(max))),
+            })
+    }
+
+    /// Draw the traces, axis ticks and the smallest & largest value into
+    /// `area` of `display`, with lines in `color` and text in `text`.
+    ///
+    /// Only the pixels that are part of the plot are drawn, so `area` should
+    /// be cleared first.
+    pub fn draw<D, S>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        color: S::Color,
+        text: &S,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = S::Color>,
+        S: TextRenderer,
+    {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let mut display = display.clipped(&area);
+        let range = self.range();
+
+        let (min, max) = match (self.scale, range) {
+            (Scale::Fixed { min, max }, _) if max - min > f32::EPSILON => (min, max),
+            (Scale::Fixed { min, max }, _) => {
+                let middle = (min + max) / 2.0;
+                (middle - 1.0, middle + 1.0)
+            }
+            (Scale::Auto, Some((min, max))) if max - min > f32::EPSILON => (min, max),
+            // Keep a flat line in the middle
+            (Scale::Auto, Some((v, _))) => (v - 1.0, v + 1.0),
+            (Scale::Auto, None) => (-1.0, 1.0),
+        };
+        let top = area.top_left.y as f32;
+        let height = (area.size.height - 1) as f32;
+        let to_y = |v: f32| (top + (max - v) / (max - min) * height) as i32;
+
+        let left = area.top_left.x;
+        let width = area.size.width as i32 - 1;
+        let to_x = |i: usize| left + (i as i32 + 1) * width / N.max(1) as i32;
+
+        // The left edge, with ticks at multiples of a round step
+        let axis = Line::new(area.top_left, Point::new(left, bottom_right.y));
+        display.draw_iter(axis.points().map(|p| Pixel(p, color)))?;
+        if let Some(step) = tick_step(max - min, self.y_ticks) {
+            // Counting steps, as adding a step much smaller than the values
+            // might not change them at all
+            let first = (min / step).ceil() as i32;
+            let last = (max / step).floor() as i32;
+            for k in (first..=last).take(self.y_ticks as usize + 1) {
+                let y = to_y(k as f32 * step);
+                display.draw_iter([1, 2].map(|dx| Pixel(Point::new(left + dx, y), color)))?;
+            }
+        }
+
+        // Ticks along the bottom, moving left with the values
+        if self.x_ticks > 0 {
+            let len = self.channels.first().map_or(0, |c| c.len());
+            for i in 0..len {
+                let age = (len - 1 - i) as u32;
+                if self.pushed.wrapping_sub(age).is_multiple_of(self.x_ticks) {
+                    let x = to_x(N - len + i);
+                    let y = bottom_right.y;
+                    display.draw_iter([0, 1].map(|dy| Pixel(Point::new(x, y - dy), color)))?;
+                }
+            }
+        }
+
+        for (channel, &pattern) in self.channels.iter().zip(&self.patterns) {
+            let offset = N - channel.len();
+            let points = channel
+                .oldest_ordered()
+                .enumerate()
+                .map(|(i, &v)| Point::new(to_x(offset + i), to_y(v)));
+            draw_trace(&mut display, points, color, pattern)?;
+        }
+
+        if let Some((min, max)) = range {
+            let at = area.top_left + Point::new(4, 0);
+            draw_value(&mut display, text, max, at, Baseline::Top)?;
+            let at = Point::new(at.x, bottom_right.y - 2);
+            draw_value(&mut display, text, min, at, Baseline::Bottom)?;
+        }
+        Ok(())
+    }
+}
+
+/// Connect `points` with lines, continuing `pattern` from one line to the
+/// next so short lines still show it.
+fn draw_trace<D>(
+    display: &mut D,
+    points: impl Iterator<Item = Point>,
+    color: D::Color,
+    pattern: LinePattern,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let mut i = 0;
+    let mut previous: Option<Point> = None;
+    for p in points {
+        let line = Line::new(previous.unwrap_or(p), p);
+        // Each line starts where the previous one ended
+        let skip = previous.is_some() as usize;
+        display.draw_iter(line.points().skip(skip).filter_map(|q| {
+            i += 1;
+            pattern.is_on(i - 1).then_some(Pixel(q, color))
+        }))?;
+        previous = Some(p);
+    }
+    Ok(())
+}
+
+fn draw_value<D, S>(
+    display: &mut D,
+    text: &S,
+    value: f32,
+    at: Point,
+    baseline: Baseline,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = S::Color>,
+    S: TextRenderer,
+{
+    let mut s: String<12> = String::new();
+    let _ = write_tenths(&mut s, value);
+    text.draw_string(&s, at, baseline, display)?;
+    Ok(())
+}
+
+/// Write `value` with one decimal, without pulling in float formatting.
+fn write_tenths(out: &mut impl fmt::Write, value: f32) -> fmt::Result {
+    let tenths = (value * 10.0 + 0.5f32.copysign(value)) as i32;
+    let sign = if tenths < 0 { "-" } else { "" };
+    let tenths = tenths.unsigned_abs();
+    write!(out, "{}{}.{}", sign, tenths / 10, tenths % 10)
+}
+
+/// A round distance (1, 2 or 5 times a power of 10) between ticks so that at
+/// most `ticks` of them cover `range`.
+fn tick_step(range: f32, ticks: u32) -> Option<f32> {
+    if ticks == 0 || range <= 0.0 || !range.is_finite() {
+        return None;
+    }
+    let rough = range / ticks as f32;
+    let magnitude = 10f32.powf(rough.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= rough)
+}
@@ -0,0 +1,308 @@
+use core::{
+    fmt::{self, Debug, Write as _},
+    ops::{Deref, DerefMut},
+};
+use cortex_m::peripheral::DWT;
+use cortex_m_semihosting::hprintln;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point},
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Baseline},
+    Pixel,
+};
+use heapless::String;
+
+/// How quickly the rolling averages follow new measurements: each new frame
+/// counts for `1 / 2^SMOOTHING`.
+const SMOOTHING: u32 = 3;
+
+/// A corner of the display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Measures the time spent in each of `N` phases of a frame, e.g. reading a
+/// sensor, drawing and flushing the display, with the DWT cycle counter.
+///
+/// The counter has to be enabled first, with `DCB::enable_trace` and
+/// `DWT::enable_cycle_counter`. Every frame starts with
+/// [`start_frame`](Self::start_frame), followed by an
+/// [`end_phase`](Self::end_phase) after each phase, in the order of the
+/// phases' names.
+///
+/// At 48 MHz the cycle counter wraps around after 89 seconds, so frames &
+/// phases have to be shorter than that.
+pub struct Profiler<const N: usize> {
+    names: [&'static str; N],
+    cycles_per_second: u32,
+    /// Rolling average of the cycles spent in each phase.
+    phases: [u32; N],
+    /// Rolling average of the cycles from the start of one frame to the next.
+    frame: u32,
+    frame_start: Option<u32>,
+    phase_start: u32,
+    phase: usize,
+}
+
+impl<const N: usize> Profiler<N> {
+    /// Profile phases called `names`, e.g. `["read", "draw", "flush"]`, with
+    /// the core running at `cycles_per_second`.
+    pub fn new(names: [&'static str; N], cycles_per_second: u32) -> Self {
+        Self {
+            names,
+            cycles_per_second,
+            phases: [0; N],
+            frame: 0,
+            frame_start: None,
+            phase_start: 0,
+            phase: 0,
+        }
+    }
+
+    /// Start a new frame, and its first phase.
+    pub fn start_frame(&mut self) {
+        let now = DWT::cycle_count();
+        if let Some(start) = self.frame_start {
+            self.frame = smooth(self.frame, now.wrapping_sub(start));
+        }
+        self.frame_start = Some(now);
+        self.phase_start = now;
+        self.phase = 0;
+    }
+
+    /// End the current phase and start the next one.
+    ///
+    /// Phases beyond the `N`th aren't measured.
+    pub fn end_phase(&mut self) {
+        let now = DWT::cycle_count();
+        if let Some(average) = self.phases.get_mut(self.phase) {
+            *average = smooth(*average, now.wrapping_sub(self.phase_start));
+        }
+        self.phase_start = now;
+        self.phase += 1;
+    }
+
+    /// End the current phase and the next one together, when they can't be
+    /// measured one after the other, with `nested` of the cycles since the
+    /// current phase started spent on the next one.
+    ///
+    /// E.g. a scene's shapes are transformed & rasterized shape by shape,
+    /// with the cycles spent rasterizing counted by a [`CycleCounted`]
+    /// display.
+    pub fn end_interleaved(&mut self, nested: u32) {
+        let now = DWT::cycle_count();
+        let total = now.wrapping_sub(self.phase_start);
+        let nested = nested.min(total);
+        for (i, cycles) in [(self.phase, total - nested), (self.phase + 1, nested)] {
+            if let Some(average) = self.phases.get_mut(i) {
+                *average = smooth(*average, cycles);
+            }
+        }
+        self.phase_start = now;
+        self.phase += 2;
+    }
+
+    /// Frames per second, times 10, or 0 before the second frame started.
+    pub fn fps_x10(&self) -> u32 {
+        if self.frame == 0 {
+            return 0;
+        }
+        (self.cycles_per_second as u64 * 10 / self.frame as u64) as u32
+    }
+
+    /// The rolling average of the cycles spent per frame.
+    pub fn frame_cycles(&self) -> u32 {
+        self.frame
+    }
+
+    /// The rolling average of the cycles spent in each phase.
+    pub fn phase_cycles(&self) -> &[u32; N] {
+        &self.phases
+    }
+
+    pub fn names(&self) -> &[&'static str; N] {
+        &self.names
+    }
+
+    /// Draw the frame rate and the time spent in each phase, one line each,
+    /// into the `corner` of `display`.
+    ///
+    /// The text is drawn with `style`, which should have a background color
+    /// to stay readable on top of whatever else is on the display.
+    pub fn draw_overlay<D, S>(&self, display: &mut D, corner: Corner, style: &S)
+    where
+        D: DrawTarget<Color = S::Color>,
+        D::Error: Debug,
+        S: TextRenderer,
+    {
+        let bounds = display.bounding_box();
+        let line_height = style.line_height() as i32;
+        let lines = N as i32 + 1;
+
+        let mut y = match corner {
+            Corner::TopLeft | Corner::TopRight => bounds.top_left.y,
+            Corner::BottomLeft | Corner::BottomRight => {
+                bounds.top_left.y + bounds.size.height as i32 - lines * line_height
+            }
+        };
+
+        for line in 0..=N {
+            let mut text: String<24> = String::new();
+            // Lines that don't fit are cut short
+            let _ = match line {
+                0 => write_tenths(&mut text, self.fps_x10() as i32)
+                    .and_then(|_| text.write_str(" fps")),
+                _ => write!(text, "{} ", self.names[line - 1])
+                    .and_then(|_| self.write_ms(&mut text, self.phases[line - 1])),
+            };
+
+            let x = match corner {
+                Corner::TopLeft | Corner::BottomLeft => bounds.top_left.x,
+                Corner::TopRight | Corner::BottomRight => {
+                    let width = style
+                        .measure_string(&text, Point::zero(), Baseline::Top)
+                        .bounding_box
+                        .size
+                        .width;
+                    bounds.top_left.x + bounds.size.width.saturating_sub(width) as i32
+                }
+            };
+
+            style
+                .draw_string(&text, Point::new(x, y), Baseline::Top, display)
+                .unwrap();
+            y += line_height;
+        }
+    }
+
+    /// Print the statistics via semihosting, which halts the core until the
+    /// debugger is done.
+    pub fn report(&self) {
+        hprintln!("{}", self);
+    }
+
+    /// Write `cycles` in milliseconds, with one decimal.
+    fn write_ms(&self, out: &mut impl fmt::Write, cycles: u32) -> fmt::Result {
+        let tenths = (cycles as u64 * 10_000 / self.cycles_per_second as u64) as i32;
+        write_tenths(out, tenths)?;
+        out.write_str("ms")
+    }
+}
+
+impl<const N: usize> fmt::Display for Profiler<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_tenths(f, self.fps_x10() as i32)?;
+        f.write_str(" fps, frame ")?;
+        self.write_ms(f, self.frame)?;
+        for (name, &cycles) in self.names.iter().zip(&self.phases) {
+            write!(f, ", {} ", name)?;
+            self.write_ms(f, cycles)?;
+        }
+        Ok(())
+    }
+}
+
+/// A display counting the cycles spent drawing onto it, i.e. rasterizing,
+/// see [`Profiler::end_interleaved`].
+///
+/// Drawing primitives hand their pixels to the display lazily, so the cycles
+/// spent working out which pixels to set are counted along with setting them.
+/// Everything else of the display is reachable through `Deref`, e.g. to flush
+/// it.
+pub struct CycleCounted<D> {
+    display: D,
+    cycles: u32,
+}
+
+impl<D> CycleCounted<D> {
+    pub fn new(display: D) -> Self {
+        Self { display, cycles: 0 }
+    }
+
+    /// The cycles spent drawing since the last call.
+    pub fn take_cycles(&mut self) -> u32 {
+        core::mem::take(&mut self.cycles)
+    }
+
+    pub fn into_inner(self) -> D {
+        self.display
+    }
+
+    fn count<T>(&mut self, draw: impl FnOnce(&mut D) -> T) -> T {
+        let start = DWT::cycle_count();
+        let result = draw(&mut self.display);
+        self.cycles = self
+            .cycles
+            .wrapping_add(DWT::cycle_count().wrapping_sub(start));
+        result
+    }
+}
+
+impl<D> Deref for CycleCounted<D> {
+    type Target = D;
+
+    fn deref(&self) -> &D {
+        &self.display
+    }
+}
+
+impl<D> DerefMut for CycleCounted<D> {
+    fn deref_mut(&mut self) -> &mut D {
+        &mut self.display
+    }
+}
+
+impl<D: DrawTarget> Dimensions for CycleCounted<D> {
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+impl<D: DrawTarget> DrawTarget for CycleCounted<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.count(|display| display.draw_iter(pixels))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.count(|display| display.fill_contiguous(area, colors))
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.count(|display| display.fill_solid(area, color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.count(|display| display.clear(color))
+    }
+}
+
+/// Write `tenths` with one decimal, e.g. `-1.5` for -15, as formatting floats
+/// takes up a lot of flash.
+pub fn write_tenths(out: &mut impl fmt::Write, tenths: i32) -> fmt::Result {
+    let sign = if tenths < 0 { "-" } else { "" };
+    let tenths = tenths.unsigned_abs();
+    write!(out, "{}{}.{}", sign, tenths / 10, tenths % 10)
+}
+
+/// Move the rolling `average` towards `value`.
+fn smooth(average: u32, value: u32) -> u32 {
+    if average == 0 {
+        // The first measurement
+        return value;
+    }
+    average - (average >> SMOOTHING) + (value >> SMOOTHING)
+}
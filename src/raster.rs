@@ -0,0 +1,108 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::Point,
+    pixelcolor::BinaryColor,
+    primitives::{Line, PointsIter as _, Triangle},
+    Pixel,
+};
+
+/// 4x4 Bayer matrix for ordered dithering.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// The color of the pixel at `point` when dithering `intensity` (from `0.0`
+/// for all off to `1.0` for all on) with a 4x4 Bayer matrix.
+///
+/// Only 17 different levels of intensity can be told apart.
+pub fn dither(intensity: f32, point: Point) -> BinaryColor {
+    let level = (intensity.clamp(0.0, 1.0) * 16.0 + 0.5) as u8;
+    let threshold = BAYER_4X4[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize];
+    if level > threshold {
+        BinaryColor::On
+    } else {
+        BinaryColor::Off
+    }
+}
+
+/// Fill the triangle `a`, `b`, `c` with `intensity`, dithered.
+///
+/// Every pixel of the triangle is drawn, including the ones that end up off,
+/// so a triangle covers whatever was drawn there before.
+pub fn fill_triangle<D>(
+    display: &mut D,
+    a: Point,
+    b: Point,
+    c: Point,
+    intensity: f32,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    display.draw_iter(
+        Triangle::new(a, b, c)
+            .points()
+            .map(|p| Pixel(p, dither(intensity, p))),
+    )
+}
+
+/// A repeating pattern of the pixels along a line, e.g. for dashed lines.
+///
+/// The `i`th pixel of a line is drawn if bit `i % len` of `bits` is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinePattern {
+    bits: u16,
+    len: u8,
+}
+
+impl LinePattern {
+    pub const SOLID: Self = Self::new(0b1, 1);
+    /// 4 pixels on, 3 off.
+    pub const DASHED: Self = Self::new(0b000_1111, 7);
+    /// Every other pixel.
+    pub const DOTTED: Self = Self::new(0b01, 2);
+    /// 4 pixels on, 1 off, 1 on, 1 off.
+    pub const DASH_DOT: Self = Self::new(0b010_1111, 7);
+
+    /// Panics unless `len` is between 1 and 16.
+    pub const fn new(bits: u16, len: u8) -> Self {
+        assert!(
+            len >= 1 && len <= 16,
+            "pattern length must be between 1 and 16"
+        );
+        Self { bits, len }
+    }
+
+    /// Whether the `i`th pixel of a line is drawn.
+    pub const fn is_on(&self, i: u32) -> bool {
+        (self.bits >> (i % self.len as u32)) & 1 != 0
+    }
+}
+
+/// Draw a 1 pixel wide line from `a` to `b` in `color`, only where `pattern`
+/// is on.
+///
+/// The pixels are also dithered from intensity `fade.0` at `a` to `fade.1` at
+/// `b`, see [`dither`], so `(1.0, 1.0)` draws every pixel of the pattern.
+pub fn draw_line<D>(
+    display: &mut D,
+    a: Point,
+    b: Point,
+    color: D::Color,
+    pattern: LinePattern,
+    fade: (f32, f32),
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let delta = b - a;
+    let steps = delta.x.abs().max(delta.y.abs()).max(1) as f32;
+    display.draw_iter(
+        Line::new(a, b)
+            .points()
+            .enumerate()
+            .filter(|&(i, p)| {
+                let intensity = fade.0 + (fade.1 - fade.0) * i as f32 / steps;
+                pattern.is_on(i as u32) && dither(intensity, p).is_on()
+            })
+            .map(|(_, p)| Pixel(p, color)),
+    )
+}
@@ -0,0 +1,274 @@
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::renderer::TextRenderer,
+};
+use heapless::Vec;
+use nalgebra::{Matrix4, Point3};
+
+use crate::{
+    camera::{Camera, Projector},
+    label::{draw_labels, Label, LabelStyle},
+    shape3d::{Placement, ShadeStyle, Shape3D, ShapeStyle},
+};
+
+/// How a [`Node`] is drawn.
+#[derive(Clone, Copy, Debug)]
+pub enum NodeStyle {
+    /// As a wireframe, see [`Shape3D::draw_projected`].
+    Wireframe(ShapeStyle<BinaryColor>),
+    /// As a shaded solid, see [`Shape3D::draw_shaded_projected`].
+    Shaded(ShadeStyle),
+}
+
+impl From<ShapeStyle<BinaryColor>> for NodeStyle {
+    fn from(style: ShapeStyle<BinaryColor>) -> Self {
+        Self::Wireframe(style)
+    }
+}
+
+impl From<PrimitiveStyle<BinaryColor>> for NodeStyle {
+    fn from(style: PrimitiveStyle<BinaryColor>) -> Self {
+        Self::Wireframe(style.into())
+    }
+}
+
+impl From<ShadeStyle> for NodeStyle {
+    fn from(style: ShadeStyle) -> Self {
+        Self::Shaded(style)
+    }
+}
+
+/// Something a [`Scene`] can draw onto a `D`, implemented by every
+/// [`Shape3D`].
+///
+/// This lets a scene hold shapes of different sizes side by side.
+pub trait SceneShape<D> {
+    /// Draw the shape transformed by `model` with `projector`.
+    fn draw_node(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: &NodeStyle,
+        model: &Matrix4<f32>,
+    );
+
+    /// The vertices [`Label`]s can be anchored to.
+    fn vertices(&self) -> &[Point3<f32>];
+}
+
+impl<D, const V: usize, const E: usize, const F: usize> SceneShape<D> for Shape3D<V, E, F>
+where
+    D: DrawTarget<Color = BinaryColor>,
+    D::Error: Debug,
+{
+    fn draw_node(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: &NodeStyle,
+        model: &Matrix4<f32>,
+    ) {
+        match style {
+            NodeStyle::Wireframe(style) => self.draw_projected(display, projector, *style, model),
+            NodeStyle::Shaded(style) => {
+                self.draw_shaded_projected(display, projector, style, model)
+            }
+        }
+    }
+
+    fn vertices(&self) -> &[Point3<f32>] {
+        &self.vertices
+    }
+}
+
+/// Refers to a [`Node`] in its [`Scene`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A shape in a [`Scene`], placed relative to its parent.
+///
+/// Nodes without a shape only group their children, e.g. to rotate them
+/// together around a common pivot.
+pub struct Node<'a, D> {
+    pub shape: Option<&'a dyn SceneShape<D>>,
+    pub style: NodeStyle,
+    /// Where the node is placed in its parent's coordinates, or in world
+    /// coordinates for nodes without a parent.
+    pub placement: Placement,
+    /// Drawn by [`Scene::draw_labels`]. Labels of nodes without a shape can
+    /// only be anchored to points.
+    pub labels: &'a [Label<'a>],
+    /// Hidden nodes aren't drawn, but their children still are.
+    pub visible: bool,
+    parent: Option<NodeId>,
+}
+
+impl<'a, D> Node<'a, D> {
+    /// A node drawing `shape` with `style`, at the origin of its parent.
+    pub fn new(shape: &'a dyn SceneShape<D>, style: impl Into<NodeStyle>) -> Self {
+        Self {
+            shape: Some(shape),
+            style: style.into(),
+            placement: Placement::default(),
+            labels: &[],
+            visible: true,
+            parent: None,
+        }
+    }
+
+    /// A node without a shape, only placing its children.
+    pub fn group() -> Self {
+        Self {
+            shape: None,
+            style: NodeStyle::Wireframe(PrimitiveStyle::new().into()),
+            placement: Placement::default(),
+            labels: &[],
+            visible: true,
+            parent: None,
+        }
+    }
+
+    pub fn with_placement(self, placement: impl Into<Placement>) -> Self {
+        Self {
+            placement: placement.into(),
+            ..self
+        }
+    }
+
+    pub fn with_labels(self, labels: &'a [Label<'a>]) -> Self {
+        Self { labels, ..self }
+    }
+
+    /// Place the node relative to `parent`.
+    pub fn with_parent(self, parent: NodeId) -> Self {
+        Self {
+            parent: Some(parent),
+            ..self
+        }
+    }
+
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+}
+
+/// Up to `N` [`Node`]s drawn together with one camera.
+///
+/// Every frame the world transform of each node is computed once, from its
+/// parent's, and all nodes share the same [`Projector`]. A node's parent is
+/// always added before the node itself, so one pass in order is enough.
+pub struct Scene<'a, D, const N: usize> {
+    nodes: Vec<Node<'a, D>, N>,
+}
+
+impl<'a, D, const N: usize> Default for Scene<'a, D, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, D, const N: usize> Scene<'a, D, N> {
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Add `node` to the scene.
+    ///
+    /// Returns the node back if the scene is full.
+    ///
+    /// Panics if the node's parent isn't part of this scene.
+    pub fn add(&mut self, node: Node<'a, D>) -> Result<NodeId, Node<'a, D>> {
+        if let Some(NodeId(parent)) = node.parent {
+            assert!(parent < self.nodes.len(), "unknown parent node");
+        }
+        self.nodes.push(node)?;
+        Ok(NodeId(self.nodes.len() - 1))
+    }
+
+    /// Panics if `id` isn't part of this scene.
+    pub fn node(&self, id: NodeId) -> &Node<'a, D> {
+        &self.nodes[id.0]
+    }
+
+    /// Panics if `id` isn't part of this scene.
+    pub fn node_mut(&mut self, id: NodeId) -> &mut Node<'a, D> {
+        &mut self.nodes[id.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// The transform from each node's model into world coordinates.
+    pub fn world_transforms(&self) -> [Matrix4<f32>; N] {
+        let mut world = [Matrix4::identity(); N];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let local = node.placement.to_homogeneous();
+            world[i] = match node.parent {
+                Some(NodeId(parent)) => world[parent] * local,
+                None => local,
+            };
+        }
+        world
+    }
+}
+
+impl<'a, D, const N: usize> Scene<'a, D, N>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    /// Draw the scene as seen by `camera` onto the whole of `display`.
+    pub fn draw(&self, display: &mut D, camera: &Camera) {
+        let viewport = display.bounding_box();
+        self.draw_in(display, viewport, camera);
+    }
+
+    /// Draw the scene as seen by `camera` into the `viewport` rectangle of
+    /// `display`.
+    pub fn draw_in(&self, display: &mut D, viewport: Rectangle, camera: &Camera) {
+        self.draw_projected(display, &camera.projector(viewport));
+    }
+
+    /// Draw the scene with a precomputed `projector`, in the order the nodes
+    /// were added.
+    pub fn draw_projected(&self, display: &mut D, projector: &Projector) {
+        let world = self.world_transforms();
+        for (node, model) in self.nodes.iter().zip(&world) {
+            if !node.visible {
+                continue;
+            }
+            if let Some(shape) = node.shape {
+                shape.draw_node(display, projector, &node.style, model);
+            }
+        }
+    }
+
+    /// Draw the labels of all visible nodes with a precomputed `projector`,
+    /// usually after [`draw_projected`](Self::draw_projected) so they're
+    /// drawn on top of all shapes.
+    pub fn draw_labels<S>(&self, display: &mut D, projector: &Projector, style: &LabelStyle<S>)
+    where
+        D::Error: Debug,
+        S: TextRenderer<Color = BinaryColor>,
+    {
+        let world = self.world_transforms();
+        for (node, model) in self.nodes.iter().zip(&world) {
+            if !node.visible || node.labels.is_empty() {
+                continue;
+            }
+            let vertices = node.shape.map_or(&[][..], |shape| shape.vertices());
+            draw_labels(display, projector, vertices, node.labels, style, model);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! A global allocator handing out a single fixed scratch buffer.
+//!
+//! `matrixmultiply` allocates a packing buffer for every multiplication and
+//! frees it again before returning, so one buffer at a time is all it needs.
+//! Its size is bounded by the block size of the multiplication kernel: for
+//! transforming vertices that's 3 by up to 64 values for A and 3 by 4 for B.
+//!
+//! Any other allocation, or one too big for the buffer, fails.
+//!
+//! The library doesn't pick a global allocator for the binaries using it, so
+//! those built with the `matrixmultiply` feature have to declare one, e.g. with
+//! [`scratch_allocator!`](crate::scratch_allocator).
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+const SIZE: usize = 1024;
+
+#[repr(align(32))]
+struct Buffer(UnsafeCell<[u8; SIZE]>);
+
+pub struct ScratchAllocator {
+    buffer: Buffer,
+    taken: AtomicBool,
+}
+
+// SAFETY: the buffer is only handed out to one owner at a time
+unsafe impl Sync for ScratchAllocator {}
+
+impl ScratchAllocator {
+    pub const fn new() -> Self {
+        Self {
+            buffer: Buffer(UnsafeCell::new([0; SIZE])),
+            taken: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for ScratchAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for ScratchAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() > SIZE
+            || layout.align() > 32
+            || self
+                .taken
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return ptr::null_mut();
+        }
+        self.buffer.0.get().cast()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        self.taken.store(false, Ordering::Release);
+    }
+}
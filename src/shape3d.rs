@@ -2,16 +2,212 @@ use core::fmt::Debug;
 use embedded_graphics::{
     draw_target::DrawTarget,
     geometry::Point,
-    primitives::{Line, PrimitiveStyle, StyledDrawable as _},
+    pixelcolor::{BinaryColor, PixelColor},
+    primitives::{Line, PrimitiveStyle, Rectangle, StyledDrawable as _},
+    text::renderer::TextRenderer,
 };
-use nalgebra::{point, vector, Matrix4, Perspective3, Point3, Vector3};
+use heapless::Vec;
+use nalgebra::{point, Isometry3, Matrix4, Point3, Translation3, UnitQuaternion, Vector3, Vector4};
 
-pub struct Shape3D<const V: usize, const E: usize> {
+use crate::{
+    camera::{Backend, Camera, Projector},
+    clip::{clip_line, clip_polygon, is_inside},
+    fixed::{fx_point, Fx, FxMatrix4},
+    label::{self, Label, LabelStyle},
+    raster::{draw_line, fill_triangle, LinePattern},
+};
+
+/// A 3D shape made of `V` vertices, `E` edges and `F` (optional) triangular
+/// faces.
+///
+/// The vertices of a face are listed counter-clockwise when looking at the
+/// face from the outside of the shape. Faces are only used to decide which
+/// edges are hidden, see [`HiddenEdges`].
+pub struct Shape3D<const V: usize, const E: usize, const F: usize = 0> {
     pub vertices: [Point3<f32>; V],
     pub edges: [(usize, usize); E],
+    pub faces: [(usize, usize, usize); F],
+}
+
+/// What to do with edges that are hidden behind the faces of their shape.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HiddenEdges {
+    /// Draw all edges, as a see-through wireframe.
+    #[default]
+    Show,
+    /// Only draw edges that belong to at least one face facing the camera
+    /// (and edges that don't belong to any face).
+    Hide,
+    /// Draw the hidden edges with this pattern instead, e.g.
+    /// [`LinePattern::DASHED`] like in a technical drawing.
+    Dashed(LinePattern),
+}
+
+/// Fades out edges the farther they are from the camera, by dithering away
+/// more and more of their pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthCue {
+    /// Up to this distance from the camera, edges are drawn fully.
+    pub near: f32,
+    /// From this distance on, edges are drawn with `min_intensity`.
+    pub far: f32,
+    /// The fraction of pixels drawn at `far`, from `0.0` to `1.0`.
+    pub min_intensity: f32,
+}
+
+impl DepthCue {
+    /// Fade edges between `near` & `far` down to a quarter of their pixels.
+    pub fn new(near: f32, far: f32) -> Self {
+        Self {
+            near,
+            far,
+            min_intensity: 0.25,
+        }
+    }
+
+    /// The intensity of an edge `depth` away from the camera, always full if
+    /// `near` & `far` are the same.
+    pub fn intensity(&self, depth: f32) -> f32 {
+        if self.far == self.near {
+            return 1.0;
+        }
+        let t = ((depth - self.near) / (self.far - self.near)).clamp(0.0, 1.0);
+        1.0 - t * (1.0 - self.min_intensity)
+    }
+}
+
+/// How to draw a [`Shape3D`].
+///
+/// A plain [`PrimitiveStyle`] converts into a `ShapeStyle` drawing all edges
+/// as solid lines.
+///
+/// Edges with a [`LinePattern`] other than [`LinePattern::SOLID`], or with a
+/// [`DepthCue`], are always 1 pixel wide.
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeStyle<C: PixelColor> {
+    pub line: PrimitiveStyle<C>,
+    pub hidden_edges: HiddenEdges,
+    /// The pattern of the (visible) edges.
+    pub pattern: LinePattern,
+    pub depth_cue: Option<DepthCue>,
+}
+
+impl<C: PixelColor> ShapeStyle<C> {
+    /// Only draw the edges that aren't hidden.
+    pub fn hidden_line(line: PrimitiveStyle<C>) -> Self {
+        Self {
+            hidden_edges: HiddenEdges::Hide,
+            ..line.into()
+        }
+    }
+
+    /// Draw the edges that are hidden dashed.
+    pub fn hidden_dashed(line: PrimitiveStyle<C>) -> Self {
+        Self {
+            hidden_edges: HiddenEdges::Dashed(LinePattern::DASHED),
+            ..line.into()
+        }
+    }
+
+    pub fn with_pattern(self, pattern: LinePattern) -> Self {
+        Self { pattern, ..self }
+    }
+
+    pub fn with_depth_cue(self, depth_cue: DepthCue) -> Self {
+        Self {
+            depth_cue: Some(depth_cue),
+            ..self
+        }
+    }
+}
+
+impl<C: PixelColor> From<PrimitiveStyle<C>> for ShapeStyle<C> {
+    fn from(line: PrimitiveStyle<C>) -> Self {
+        Self {
+            line,
+            hidden_edges: HiddenEdges::Show,
+            pattern: LinePattern::SOLID,
+            depth_cue: None,
+        }
+    }
+}
+
+/// How to draw a [`Shape3D`] as a shaded solid, see
+/// [`Shape3D::draw_shaded`].
+#[derive(Clone, Copy, Debug)]
+pub struct ShadeStyle {
+    /// Direction from the shape towards the light, in world coordinates.
+    pub light: Vector3<f32>,
+    /// Intensity of faces facing away from the light.
+    pub ambient: f32,
+    /// Style for the visible edges drawn on top of the faces, if any.
+    pub outline: Option<PrimitiveStyle<BinaryColor>>,
+}
+
+impl Default for ShadeStyle {
+    fn default() -> Self {
+        Self {
+            light: Vector3::new(-0.5, 1.0, -1.0),
+            ambient: 0.1,
+            outline: None,
+        }
+    }
+}
+
+/// Where a [`Shape3D`] is drawn: rotated & translated by `isometry` after being
+/// scaled by `scale`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Placement {
+    pub isometry: Isometry3<f32>,
+    pub scale: f32,
+}
+
+impl Placement {
+    pub fn new(isometry: Isometry3<f32>, scale: f32) -> Self {
+        Self { isometry, scale }
+    }
+
+    /// Rotated by `rotation` around the origin and scaled by `scale`.
+    pub fn from_rotation(rotation: UnitQuaternion<f32>, scale: f32) -> Self {
+        Self::new(
+            Isometry3::from_parts(Translation3::identity(), rotation),
+            scale,
+        )
+    }
+
+    /// At `position` with the shape's Y axis pointing towards `face_towards`,
+    /// which must not be straight above or below `position`.
+    pub fn face_towards(position: &Point3<f32>, face_towards: &Point3<f32>) -> Self {
+        let isometry = Isometry3::face_towards(position, face_towards, &Vector3::y())
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), core::f32::consts::FRAC_PI_2);
+        Self::new(isometry, 1.0)
+    }
+
+    /// The model matrix for this placement.
+    pub fn to_homogeneous(&self) -> Matrix4<f32> {
+        self.isometry.to_homogeneous().prepend_scaling(self.scale)
+    }
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::new(Isometry3::identity(), 1.0)
+    }
+}
+
+impl From<Isometry3<f32>> for Placement {
+    fn from(isometry: Isometry3<f32>) -> Self {
+        Self::new(isometry, 1.0)
+    }
+}
+
+impl From<UnitQuaternion<f32>> for Placement {
+    fn from(rotation: UnitQuaternion<f32>) -> Self {
+        Self::from_rotation(rotation, 1.0)
+    }
 }
 
-pub const ARROW: Shape3D<6, 9> = {
+pub const ARROW: Shape3D<6, 9, 6> = {
     let length = 1.0;
     let head_width = 0.25;
     let head_height = 0.5;
@@ -35,10 +231,18 @@ pub const ARROW: Shape3D<6, 9> = {
             (4, 5),
             (5, 2),
         ],
+        faces: [
+            (1, 3, 2),
+            (1, 4, 3),
+            (1, 5, 4),
+            (1, 2, 5),
+            (2, 3, 4),
+            (2, 4, 5),
+        ],
     }
 };
 
-pub const CUBOID: Shape3D<8, 12> = {
+pub const CUBOID: Shape3D<8, 12, 12> = {
     let width = 1.0;
     let depth = 1.25;
     let height = 0.125;
@@ -68,45 +272,458 @@ pub const CUBOID: Shape3D<8, 12> = {
             (2, 6),
             (3, 7),
         ],
+        faces: [
+            (0, 1, 2),
+            (0, 2, 3),
+            (4, 6, 5),
+            (4, 7, 6),
+            (0, 4, 5),
+            (0, 5, 1),
+            (3, 2, 6),
+            (3, 6, 7),
+            (0, 3, 7),
+            (0, 7, 4),
+            (1, 5, 6),
+            (1, 6, 2),
+        ],
     }
 };
 
-impl<const V: usize, const E: usize> Shape3D<V, E> {
+impl<const V: usize, const E: usize, const F: usize> Shape3D<V, E, F> {
+    /// Draw the shape placed by `placement`, e.g. an [`Isometry3`], the
+    /// [`UnitQuaternion`] from an attitude estimator or
+    /// [`Placement::face_towards`], as seen by `camera` into the `viewport`
+    /// rectangle of `display`, or all of it for `None`.
+    ///
+    /// The aspect ratio of the projection is taken from the viewport, so
+    /// several views can be laid out next to each other on the same display.
     pub fn draw<D>(
         &self,
         display: &mut D,
-        line_style: PrimitiveStyle<D::Color>,
-        position: &Point3<f32>,
-        face_towards: &Point3<f32>,
+        style: impl Into<ShapeStyle<D::Color>>,
+        camera: &Camera,
+        placement: impl Into<Placement>,
+        viewport: Option<Rectangle>,
+    ) where
+        D: DrawTarget,
+        D::Error: Debug,
+    {
+        let viewport = viewport.unwrap_or_else(|| display.bounding_box());
+        let model = placement.into().to_homogeneous();
+        self.draw_projected(display, &camera.projector(viewport), style, &model);
+    }
+
+    /// Draw the shape as a solid, otherwise like [`draw`](Self::draw).
+    ///
+    /// Every face facing the camera is filled with a dithered intensity
+    /// depending on the angle between the face and the light. The faces are
+    /// drawn back to front, so nearer faces cover farther ones (which works
+    /// as long as faces don't intersect). Edges that don't belong to any face
+    /// aren't drawn.
+    pub fn draw_shaded<D>(
+        &self,
+        display: &mut D,
+        style: &ShadeStyle,
+        camera: &Camera,
+        placement: impl Into<Placement>,
+        viewport: Option<Rectangle>,
+    ) where
+        D: DrawTarget<Color = BinaryColor>,
+        D::Error: Debug,
+    {
+        let viewport = viewport.unwrap_or_else(|| display.bounding_box());
+        let model = placement.into().to_homogeneous();
+        self.draw_shaded_projected(display, &camera.projector(viewport), style, &model);
+    }
+
+    /// Draw the shape transformed by `model` with a precomputed `projector`.
+    ///
+    /// This is what [`draw`](Self::draw) ends up calling. When drawing
+    /// several shapes with the same camera, creating the [`Projector`] once
+    /// and calling this directly saves recomputing the view & projection for
+    /// every shape. The projector's [`Backend`] decides whether the vertices
+    /// are transformed in floating or fixed-point.
+    pub fn draw_projected<D>(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: impl Into<ShapeStyle<D::Color>>,
+        model: &Matrix4<f32>,
     ) where
         D: DrawTarget,
         D::Error: Debug,
     {
-        let model = Matrix4::face_towards(position, face_towards, &Vector3::y())
-            * Matrix4::from_axis_angle(&Vector3::x_axis(), core::f32::consts::FRAC_PI_2);
-
-        let view = Matrix4::look_at_rh(
-            &Point3::new(0.0, 0.0, -2.0),
-            &Point3::new(0.0, 0.0, 0.0),
-            &Vector3::y(),
-        );
-        let proj = Perspective3::new(2.0, 60f32.to_radians(), 0.01, 100.0).to_homogeneous();
-        // let proj = Orthographic3::new(-2.0, 2.0, -1.0, 1.0, 0.01, 100.0).to_homogeneous();
-        let screen = Matrix4::new_nonuniform_scaling(&vector![128.0 / 2.0, 64.0 / 2.0, 1.0])
-            .prepend_translation(&vector![1.0, 1.0, 0.0]);
-
-        let spvm = screen * proj * view * model;
-
-        let transform = |p: Point3<f32>| -> Point {
-            let pss = spvm.transform_point(&p);
-            Point::new(pss.x as i32, pss.y as i32)
+        let mut clip = [Vector4::zeros(); V];
+        let mut screen = [None; V];
+        let mut front = [true; F];
+        let scratch = Scratch {
+            clip: &mut clip,
+            screen: &mut screen,
+            front: &mut front,
+            order: &mut [],
         };
+        self.mesh()
+            .draw_projected(display, projector, &style.into(), model, scratch);
+    }
 
-        for (f, t) in self.edges {
-            let vf = transform(self.vertices[f]);
-            let vt = transform(self.vertices[t]);
+    /// Draw the shape transformed by `model` as a solid with a precomputed
+    /// `projector`, see [`draw_projected`](Self::draw_projected) and
+    /// [`draw_shaded`](Self::draw_shaded).
+    ///
+    /// Only the vertices are transformed with the projector's [`Backend`],
+    /// clipping & shading always use floating-point.
+    pub fn draw_shaded_projected<D>(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: &ShadeStyle,
+        model: &Matrix4<f32>,
+    ) where
+        D: DrawTarget<Color = BinaryColor>,
+        D::Error: Debug,
+    {
+        let mut clip = [Vector4::zeros(); V];
+        let mut screen = [None; V];
+        let mut front = [false; F];
+        let mut order = [(0.0, 0); F];
+        let scratch = Scratch {
+            clip: &mut clip,
+            screen: &mut screen,
+            front: &mut front,
+            order: &mut order,
+        };
+        self.mesh()
+            .draw_shaded_projected(display, projector, style, model, scratch);
+    }
+
+    /// Draw `labels` anchored to the shape transformed by `model` with a
+    /// precomputed `projector`, usually right after drawing the shape itself
+    /// with [`draw_projected`](Self::draw_projected).
+    ///
+    /// Labels behind the camera or off-screen are skipped, see
+    /// [`label::draw_labels`](crate::label::draw_labels).
+    pub fn draw_labels<D, S>(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        labels: &[Label],
+        style: &LabelStyle<S>,
+        model: &Matrix4<f32>,
+    ) where
+        D: DrawTarget<Color = S::Color>,
+        D::Error: Debug,
+        S: TextRenderer,
+    {
+        label::draw_labels(display, projector, &self.vertices, labels, style, model);
+    }
+
+    /// The shape without its size in the type, so the drawing code is only
+    /// compiled once for all shapes.
+    fn mesh(&self) -> Mesh<'_> {
+        Mesh {
+            vertices: &self.vertices,
+            edges: &self.edges,
+            faces: &self.faces,
+        }
+    }
+}
+
+/// A borrowed [`Shape3D`] of any size.
+struct Mesh<'a> {
+    vertices: &'a [Point3<f32>],
+    edges: &'a [(usize, usize)],
+    faces: &'a [(usize, usize, usize)],
+}
+
+/// Room for drawing a [`Mesh`], on the stack of the [`Shape3D`] method that
+/// knows its size.
+struct Scratch<'a> {
+    /// Each vertex in clip coordinates.
+    clip: &'a mut [Vector4<f32>],
+    /// Each vertex on the screen, if it's inside the view frustum.
+    screen: &'a mut [Option<Point>],
+    /// Whether each face is facing the camera.
+    front: &'a mut [bool],
+    /// Depth & index of the faces to draw, only used for shading.
+    order: &'a mut [(f32, usize)],
+}
+
+impl Mesh<'_> {
+    /// See [`Shape3D::draw_projected`].
+    fn draw_projected<D>(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: &ShapeStyle<D::Color>,
+        model: &Matrix4<f32>,
+        scratch: Scratch,
+    ) where
+        D: DrawTarget,
+        D::Error: Debug,
+    {
+        if style.hidden_edges != HiddenEdges::Show {
+            self.front_faces(projector, model, scratch.front);
+        }
 
-            Line::new(vf, vt).draw_styled(&line_style, display).unwrap();
+        self.project_vertices(projector, model, scratch.clip, scratch.screen);
+
+        for &(f, t) in self.edges {
+            let pattern = if self.is_edge_visible((f, t), scratch.front) {
+                style.pattern
+            } else if let HiddenEdges::Dashed(pattern) = style.hidden_edges {
+                pattern
+            } else {
+                continue;
+            };
+
+            let Some(edge) = project_edge(projector, (f, t), scratch.clip, scratch.screen) else {
+                continue;
+            };
+            let (a, b) = edge.screen;
+
+            if pattern == LinePattern::SOLID && style.depth_cue.is_none() {
+                Line::new(a, b).draw_styled(&style.line, display).unwrap();
+            } else if let Some(color) = style.line.stroke_color {
+                let fade = match &style.depth_cue {
+                    Some(cue) => (
+                        cue.intensity(projector.depth(&edge.clip.0)),
+                        cue.intensity(projector.depth(&edge.clip.1)),
+                    ),
+                    None => (1.0, 1.0),
+                };
+                draw_line(display, a, b, color, pattern, fade).unwrap();
+            }
         }
     }
+
+    /// See [`Shape3D::draw_shaded_projected`].
+    fn draw_shaded_projected<D>(
+        &self,
+        display: &mut D,
+        projector: &Projector,
+        style: &ShadeStyle,
+        model: &Matrix4<f32>,
+        scratch: Scratch,
+    ) where
+        D: DrawTarget<Color = BinaryColor>,
+        D::Error: Debug,
+    {
+        let Scratch {
+            clip,
+            screen,
+            front,
+            order,
+        } = scratch;
+
+        let view_from_model = projector.view() * model;
+
+        self.front_faces(projector, model, front);
+        self.project_vertices(projector, model, clip, screen);
+        let light = style.light.normalize();
+
+        // Painter's algorithm: sort the visible faces by depth, farthest first
+        let mut count = 0;
+        for (i, &(a, b, c)) in self.faces.iter().enumerate() {
+            if front[i] {
+                let centroid =
+                    (self.vertices[a].coords + self.vertices[b].coords + self.vertices[c].coords)
+                        / 3.0;
+                let depth = view_from_model.transform_point(&Point3::from(centroid)).z;
+                order[count] = (depth, i);
+                count += 1;
+            }
+        }
+        let order = &mut order[..count];
+        order.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+        for &(_, i) in order.iter() {
+            let (a, b, c) = self.faces[i];
+
+            let (va, vb, vc) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+            let normal = model.transform_vector(&(vb - va).cross(&(vc - va)));
+            let lambert = normal.normalize().dot(&light).max(0.0);
+            let intensity = style.ambient + (1.0 - style.ambient) * lambert;
+
+            if let (Some(sa), Some(sb), Some(sc)) = (screen[a], screen[b], screen[c]) {
+                fill_triangle(display, sa, sb, sc, intensity).unwrap();
+                continue;
+            }
+
+            let polygon: Vec<_, 9> = clip_polygon(&[clip[a], clip[b], clip[c]]);
+            if polygon.len() < 3 {
+                continue;
+            }
+
+            let first = projector.to_screen(polygon[0]);
+            for pair in polygon[1..].windows(2) {
+                fill_triangle(
+                    display,
+                    first,
+                    projector.to_screen(pair[0]),
+                    projector.to_screen(pair[1]),
+                    intensity,
+                )
+                .unwrap();
+            }
+        }
+
+        if let Some(outline) = &style.outline {
+            for &(f, t) in self.edges {
+                if !self.is_edge_visible((f, t), front) || !self.has_edge((f, t)) {
+                    continue;
+                }
+
+                if let Some(ProjectedEdge { screen: (a, b), .. }) =
+                    project_edge(projector, (f, t), clip, screen)
+                {
+                    Line::new(a, b).draw_styled(outline, display).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Transform every vertex into `clip` coordinates, once, and onto the
+    /// `screen` if it's inside the view frustum.
+    fn project_vertices(
+        &self,
+        projector: &Projector,
+        model: &Matrix4<f32>,
+        clip: &mut [Vector4<f32>],
+        screen: &mut [Option<Point>],
+    ) {
+        match projector.backend() {
+            Backend::Float => {
+                transform_vertices(&(projector.clip_from_world() * model), self.vertices, clip);
+                for (screen, c) in screen.iter_mut().zip(&*clip) {
+                    *screen = is_inside(c).then(|| projector.to_screen(*c));
+                }
+            }
+            Backend::Fixed => {
+                let clip_from_model =
+                    projector.clip_from_world_fx() * &FxMatrix4::from_matrix(model);
+                let vertices = self.vertices.iter().zip(clip).zip(screen);
+                for ((v, clip), screen) in vertices {
+                    let c = clip_from_model.transform_point(&fx_point(v));
+                    *screen = is_inside_fx(&c).then(|| projector.to_screen_fx(c));
+                    *clip = c.map(Fx::to_f32).into();
+                }
+            }
+        }
+    }
+
+    /// Which faces are facing the camera, written into `front`.
+    fn front_faces(&self, projector: &Projector, model: &Matrix4<f32>, front: &mut [bool]) {
+        let view_from_model = projector.view() * model;
+        let camera = projector.camera();
+
+        for (front, &(a, b, c)) in front.iter_mut().zip(self.faces) {
+            let a = view_from_model.transform_point(&self.vertices[a]);
+            let b = view_from_model.transform_point(&self.vertices[b]);
+            let c = view_from_model.transform_point(&self.vertices[c]);
+
+            let normal = (b - a).cross(&(c - a));
+            *front = normal.dot(&camera.view_direction(&a)) < 0.0;
+        }
+    }
+
+    /// Whether an edge belongs to any face.
+    fn has_edge(&self, (f, t): (usize, usize)) -> bool {
+        self.faces.iter().any(|&(a, b, c)| {
+            let has = |v| v == a || v == b || v == c;
+            has(f) && has(t)
+        })
+    }
+
+    /// Whether an edge belongs to a visible face, or to no face at all.
+    fn is_edge_visible(&self, (f, t): (usize, usize), visible: &[bool]) -> bool {
+        let mut in_face = false;
+        for (&visible, &(a, b, c)) in visible.iter().zip(self.faces) {
+            let has = |v| v == a || v == b || v == c;
+            if has(f) && has(t) {
+                if visible {
+                    return true;
+                }
+                in_face = true;
+            }
+        }
+        !in_face
+    }
+}
+
+/// Transform all `vertices` by `m` into `out`.
+///
+/// With the `matrixmultiply` feature that's a single matrix multiplication:
+/// with the vertices as the rows of a matrix, `out = vertices * mᵀ`, where the
+/// implicit `w = 1` of the vertices is taken care of by starting out with the
+/// translation (the last column of `m`) in every row of `out`.
+fn transform_vertices(m: &Matrix4<f32>, vertices: &[Point3<f32>], out: &mut [Vector4<f32>]) {
+    assert_eq!(vertices.len(), out.len());
+
+    #[cfg(feature = "matrixmultiply")]
+    {
+        out.fill(m.column(3).into_owned());
+
+        // SAFETY: `Point3<f32>` & `Vector4<f32>` are laid out as 3 & 4
+        // consecutive `f32`s, `m` is stored column by column and `out` has
+        // room for all vertices.
+        unsafe {
+            matrixmultiply::sgemm(
+                vertices.len(),
+                3,
+                4,
+                1.0,
+                vertices.as_ptr().cast(),
+                3,
+                1,
+                m.as_ptr(),
+                4,
+                1,
+                1.0,
+                out.as_mut_ptr().cast(),
+                4,
+                1,
+            );
+        }
+    }
+
+    #[cfg(not(feature = "matrixmultiply"))]
+    for (v, out) in vertices.iter().zip(out) {
+        *out = m * v.to_homogeneous();
+    }
+}
+
+/// The visible part of an edge.
+struct ProjectedEdge {
+    screen: (Point, Point),
+    clip: (Vector4<f32>, Vector4<f32>),
+}
+
+/// The visible part of the edge `(f, t)`, if any, from the vertices' cached
+/// `clip` & `screen` coordinates.
+fn project_edge(
+    projector: &Projector,
+    (f, t): (usize, usize),
+    clip: &[Vector4<f32>],
+    screen: &[Option<Point>],
+) -> Option<ProjectedEdge> {
+    match (screen[f], screen[t]) {
+        (Some(a), Some(b)) => Some(ProjectedEdge {
+            screen: (a, b),
+            clip: (clip[f], clip[t]),
+        }),
+        _ => {
+            let (a, b) = clip_line(clip[f], clip[t])?;
+            Some(ProjectedEdge {
+                screen: (projector.to_screen(a), projector.to_screen(b)),
+                clip: (a, b),
+            })
+        }
+    }
+}
+
+/// Whether a point in fixed-point clip coordinates is inside the view frustum
+/// (and safe to divide by `w`).
+fn is_inside_fx(c: &[Fx; 4]) -> bool {
+    let w = c[3];
+    w > Fx::ZERO && c[0].abs() <= w && c[1].abs() <= w && c[2].abs() <= w
 }
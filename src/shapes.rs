@@ -0,0 +1,535 @@
+//! Generators for common 3D shapes.
+//!
+//! All generators are `const fn`s, so they can build a [`Shape3D`] at compile
+//! time as well as at runtime. Because the number of vertices, edges & faces
+//! can't be computed from the generator's arguments in the type, they have to
+//! be spelled out; the `*_size` functions calculate them:
+//!
+//! ```ignore
+//! const SIZE: ShapeSize = uv_sphere_size(8, 4);
+//! const SPHERE: Shape3D<{ SIZE.vertices }, { SIZE.edges }, { SIZE.faces }> =
+//!     uv_sphere(8, 4, 0.5);
+//! ```
+//!
+//! Every shape is centered on the origin, shapes with an axis of symmetry
+//! have it along the Y axis.
+
+use core::f32::consts::{PI, TAU};
+use nalgebra::Point3;
+
+use crate::shape3d::Shape3D;
+
+/// The number of vertices, edges & faces of a generated shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeSize {
+    pub vertices: usize,
+    pub edges: usize,
+    pub faces: usize,
+}
+
+/// The size of a [`uv_sphere`].
+pub const fn uv_sphere_size(segments: usize, rings: usize) -> ShapeSize {
+    ShapeSize {
+        vertices: segments * (rings - 1) + 2,
+        edges: segments * (2 * rings - 1),
+        faces: 2 * segments * (rings - 1),
+    }
+}
+
+/// A sphere made of `rings` bands of latitude from pole to pole, each split
+/// into `segments` along the longitude.
+///
+/// Panics unless `segments >= 3`, `rings >= 2` and the size matches
+/// [`uv_sphere_size`].
+pub const fn uv_sphere<const V: usize, const E: usize, const F: usize>(
+    segments: usize,
+    rings: usize,
+    radius: f32,
+) -> Shape3D<V, E, F> {
+    assert!(segments >= 3 && rings >= 2);
+
+    let mut b = Builder::new();
+    let top = b.vertex(0.0, radius, 0.0);
+    let mut ring = 1;
+    while ring < rings {
+        let (sin_theta, cos_theta) = sin_cos(PI * ring as f32 / rings as f32);
+        b.ring(segments, radius * sin_theta, radius * cos_theta);
+        ring += 1;
+    }
+    let bottom = b.vertex(0.0, -radius, 0.0);
+
+    b.cap(segments, top, 1, false);
+    let mut ring = 1;
+    while ring + 1 < rings {
+        b.band(segments, 1 + (ring - 1) * segments, 1 + ring * segments);
+        ring += 1;
+    }
+    b.cap(segments, bottom, 1 + (rings - 2) * segments, true);
+
+    b.finish()
+}
+
+/// The size of a [`cylinder`].
+pub const fn cylinder_size(segments: usize) -> ShapeSize {
+    ShapeSize {
+        vertices: 2 * segments,
+        edges: 3 * segments,
+        faces: 4 * segments - 4,
+    }
+}
+
+/// A cylinder whose round sides are approximated by `segments` flat ones.
+///
+/// Panics unless `segments >= 3` and the size matches [`cylinder_size`].
+pub const fn cylinder<const V: usize, const E: usize, const F: usize>(
+    segments: usize,
+    radius: f32,
+    height: f32,
+) -> Shape3D<V, E, F> {
+    assert!(segments >= 3);
+
+    let mut b = Builder::new();
+    b.ring(segments, radius, height / 2.0);
+    b.ring(segments, radius, -height / 2.0);
+
+    b.disc(segments, 0, false);
+    b.band(segments, 0, segments);
+    b.disc(segments, segments, true);
+
+    b.finish()
+}
+
+/// The size of a [`cone`].
+pub const fn cone_size(segments: usize) -> ShapeSize {
+    ShapeSize {
+        vertices: segments + 1,
+        edges: 2 * segments,
+        faces: 2 * segments - 2,
+    }
+}
+
+/// A cone pointing up, its round side approximated by `segments` flat ones.
+///
+/// Panics unless `segments >= 3` and the size matches [`cone_size`].
+pub const fn cone<const V: usize, const E: usize, const F: usize>(
+    segments: usize,
+    radius: f32,
+    height: f32,
+) -> Shape3D<V, E, F> {
+    assert!(segments >= 3);
+
+    let mut b = Builder::new();
+    let apex = b.vertex(0.0, height / 2.0, 0.0);
+    b.ring(segments, radius, -height / 2.0);
+
+    b.cap(segments, apex, 1, false);
+    b.disc(segments, 1, true);
+
+    b.finish()
+}
+
+/// The size of a [`torus`].
+pub const fn torus_size(segments: usize, sides: usize) -> ShapeSize {
+    ShapeSize {
+        vertices: segments * sides,
+        edges: 2 * segments * sides,
+        faces: 2 * segments * sides,
+    }
+}
+
+/// A torus lying in the XZ plane, made of `segments` pieces of a tube with
+/// `sides` flat sides.
+///
+/// `major_radius` is the distance from the center to the middle of the tube,
+/// `minor_radius` the radius of the tube.
+///
+/// Panics unless `segments >= 3`, `sides >= 3` and the size matches
+/// [`torus_size`].
+pub const fn torus<const V: usize, const E: usize, const F: usize>(
+    segments: usize,
+    sides: usize,
+    major_radius: f32,
+    minor_radius: f32,
+) -> Shape3D<V, E, F> {
+    assert!(segments >= 3 && sides >= 3);
+
+    let mut b = Builder::new();
+    let mut side = 0;
+    while side < sides {
+        let (sin, cos) = sin_cos(TAU * side as f32 / sides as f32);
+        b.ring(
+            segments,
+            major_radius + minor_radius * cos,
+            minor_radius * sin,
+        );
+        side += 1;
+    }
+
+    let mut side = 0;
+    while side < sides {
+        b.band(segments, ((side + 1) % sides) * segments, side * segments);
+        side += 1;
+    }
+
+    b.finish()
+}
+
+/// An XYZ axis gizmo: an arrow of `length` from the origin along each of the
+/// positive axes.
+pub const fn axes(length: f32) -> Shape3D<10, 9> {
+    let head = length * 0.2;
+
+    let mut b = Builder::new();
+    let origin = b.vertex(0.0, 0.0, 0.0);
+    let tips = [
+        b.vertex(length, 0.0, 0.0),
+        b.vertex(0.0, length, 0.0),
+        b.vertex(0.0, 0.0, length),
+    ];
+    let barbs = [
+        b.vertex(length - head, head / 2.0, 0.0),
+        b.vertex(length - head, -head / 2.0, 0.0),
+        b.vertex(head / 2.0, length - head, 0.0),
+        b.vertex(-head / 2.0, length - head, 0.0),
+        b.vertex(0.0, head / 2.0, length - head),
+        b.vertex(0.0, -head / 2.0, length - head),
+    ];
+
+    let mut axis = 0;
+    while axis < 3 {
+        b.edge(origin, tips[axis]);
+        b.edge(tips[axis], barbs[2 * axis]);
+        b.edge(tips[axis], barbs[2 * axis + 1]);
+        axis += 1;
+    }
+
+    b.finish()
+}
+
+/// The golden ratio.
+const PHI: f32 = 1.618034;
+/// `1 / PHI`
+const INV_PHI: f32 = PHI - 1.0;
+
+pub const TETRAHEDRON: Shape3D<4, 6, 4> = polyhedron(
+    [
+        [1.0, 1.0, 1.0],
+        [1.0, -1.0, -1.0],
+        [-1.0, 1.0, -1.0],
+        [-1.0, -1.0, 1.0],
+    ],
+    [[2, 0, 1], [1, 0, 3], [3, 0, 2], [2, 1, 3]],
+);
+
+/// A cube, the regular hexahedron.
+pub const CUBE: Shape3D<8, 12, 12> = polyhedron(
+    [
+        [-1.0, -1.0, -1.0],
+        [-1.0, -1.0, 1.0],
+        [-1.0, 1.0, -1.0],
+        [-1.0, 1.0, 1.0],
+        [1.0, -1.0, -1.0],
+        [1.0, -1.0, 1.0],
+        [1.0, 1.0, -1.0],
+        [1.0, 1.0, 1.0],
+    ],
+    [
+        [2, 0, 1, 3],
+        [1, 0, 4, 5],
+        [4, 0, 2, 6],
+        [3, 1, 5, 7],
+        [6, 2, 3, 7],
+        [5, 4, 6, 7],
+    ],
+);
+
+pub const OCTAHEDRON: Shape3D<6, 12, 8> = polyhedron(
+    [
+        [1.0, 0.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+    ],
+    [
+        [4, 0, 2],
+        [2, 0, 5],
+        [3, 0, 4],
+        [5, 0, 3],
+        [2, 1, 4],
+        [5, 1, 2],
+        [4, 1, 3],
+        [3, 1, 5],
+    ],
+);
+
+pub const DODECAHEDRON: Shape3D<20, 30, 36> = polyhedron(
+    [
+        [-1.0, -1.0, -1.0],
+        [-1.0, -1.0, 1.0],
+        [-1.0, 1.0, -1.0],
+        [-1.0, 1.0, 1.0],
+        [1.0, -1.0, -1.0],
+        [1.0, -1.0, 1.0],
+        [1.0, 1.0, -1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, -INV_PHI, -PHI],
+        [-INV_PHI, -PHI, 0.0],
+        [-PHI, 0.0, -INV_PHI],
+        [0.0, -INV_PHI, PHI],
+        [-INV_PHI, PHI, 0.0],
+        [PHI, 0.0, -INV_PHI],
+        [0.0, INV_PHI, -PHI],
+        [INV_PHI, -PHI, 0.0],
+        [-PHI, 0.0, INV_PHI],
+        [0.0, INV_PHI, PHI],
+        [INV_PHI, PHI, 0.0],
+        [PHI, 0.0, INV_PHI],
+    ],
+    [
+        [16, 10, 0, 9, 1],
+        [14, 8, 0, 10, 2],
+        [15, 9, 0, 8, 4],
+        [3, 16, 1, 11, 17],
+        [5, 11, 1, 9, 15],
+        [3, 12, 2, 10, 16],
+        [6, 14, 2, 12, 18],
+        [18, 12, 3, 17, 7],
+        [5, 15, 4, 13, 19],
+        [6, 13, 4, 8, 14],
+        [17, 11, 5, 19, 7],
+        [19, 13, 6, 18, 7],
+    ],
+);
+
+pub const ICOSAHEDRON: Shape3D<12, 30, 20> = polyhedron(
+    [
+        [0.0, -1.0, -PHI],
+        [-1.0, -PHI, 0.0],
+        [-PHI, 0.0, -1.0],
+        [0.0, -1.0, PHI],
+        [-1.0, PHI, 0.0],
+        [PHI, 0.0, -1.0],
+        [0.0, 1.0, -PHI],
+        [1.0, -PHI, 0.0],
+        [-PHI, 0.0, 1.0],
+        [0.0, 1.0, PHI],
+        [1.0, PHI, 0.0],
+        [PHI, 0.0, 1.0],
+    ],
+    [
+        [2, 0, 1],
+        [1, 0, 7],
+        [6, 0, 2],
+        [5, 0, 6],
+        [7, 0, 5],
+        [2, 1, 8],
+        [3, 1, 7],
+        [8, 1, 3],
+        [6, 2, 4],
+        [4, 2, 8],
+        [11, 3, 7],
+        [8, 3, 9],
+        [9, 3, 11],
+        [6, 4, 10],
+        [9, 4, 8],
+        [10, 4, 9],
+        [10, 5, 6],
+        [7, 5, 11],
+        [11, 5, 10],
+        [10, 9, 11],
+    ],
+);
+
+/// A regular polyhedron with its vertices scaled onto the unit sphere, from
+/// convex `polygons` listed counter-clockwise when seen from the outside.
+const fn polyhedron<
+    const V: usize,
+    const E: usize,
+    const F: usize,
+    const N: usize,
+    const P: usize,
+>(
+    vertices: [[f32; 3]; V],
+    polygons: [[usize; N]; P],
+) -> Shape3D<V, E, F> {
+    let [x, y, z] = vertices[0];
+    let scale = 1.0 / sqrt(x * x + y * y + z * z);
+
+    let mut b = Builder::new();
+    let mut i = 0;
+    while i < V {
+        let [x, y, z] = vertices[i];
+        b.vertex(x * scale, y * scale, z * scale);
+        i += 1;
+    }
+
+    let mut p = 0;
+    while p < P {
+        let polygon = polygons[p];
+        let mut i = 0;
+        while i < N {
+            b.edge(polygon[i], polygon[(i + 1) % N]);
+            if i >= 2 {
+                b.face(polygon[0], polygon[i - 1], polygon[i]);
+            }
+            i += 1;
+        }
+        p += 1;
+    }
+
+    b.finish()
+}
+
+/// Fills in a [`Shape3D`] one vertex, edge & face at a time.
+struct Builder<const V: usize, const E: usize, const F: usize> {
+    shape: Shape3D<V, E, F>,
+    vertices: usize,
+    edges: usize,
+    faces: usize,
+}
+
+impl<const V: usize, const E: usize, const F: usize> Builder<V, E, F> {
+    const fn new() -> Self {
+        Self {
+            shape: Shape3D {
+                vertices: [Point3::new(0.0, 0.0, 0.0); V],
+                edges: [(0, 0); E],
+                faces: [(0, 0, 0); F],
+            },
+            vertices: 0,
+            edges: 0,
+            faces: 0,
+        }
+    }
+
+    /// Adds a vertex, returning its index.
+    const fn vertex(&mut self, x: f32, y: f32, z: f32) -> usize {
+        assert!(self.vertices < V, "too many vertices");
+        self.shape.vertices[self.vertices] = Point3::new(x, y, z);
+        self.vertices += 1;
+        self.vertices - 1
+    }
+
+    /// Adds the edge from `a` to `b`, unless it's already there.
+    const fn edge(&mut self, a: usize, b: usize) {
+        let mut i = 0;
+        while i < self.edges {
+            let (c, d) = self.shape.edges[i];
+            if (a == c && b == d) || (a == d && b == c) {
+                return;
+            }
+            i += 1;
+        }
+
+        assert!(self.edges < E, "too many edges");
+        self.shape.edges[self.edges] = (a, b);
+        self.edges += 1;
+    }
+
+    const fn face(&mut self, a: usize, b: usize, c: usize) {
+        assert!(self.faces < F, "too many faces");
+        self.shape.faces[self.faces] = (a, b, c);
+        self.faces += 1;
+    }
+
+    /// Adds `segments` vertices on a horizontal circle of `radius` at height
+    /// `y`, counter-clockwise when seen from below.
+    const fn ring(&mut self, segments: usize, radius: f32, y: f32) {
+        let mut i = 0;
+        while i < segments {
+            let (sin, cos) = sin_cos(TAU * i as f32 / segments as f32);
+            self.vertex(radius * cos, y, radius * sin);
+            i += 1;
+        }
+    }
+
+    /// Connects the ring starting at vertex `upper` with the one below it
+    /// starting at vertex `lower`, with edges along the lower ring and
+    /// between the rings.
+    const fn band(&mut self, segments: usize, upper: usize, lower: usize) {
+        let mut i = 0;
+        while i < segments {
+            let next = (i + 1) % segments;
+            self.edge(upper + i, lower + i);
+            self.edge(lower + i, lower + next);
+            self.face(upper + i, upper + next, lower + next);
+            self.face(upper + i, lower + next, lower + i);
+            i += 1;
+        }
+    }
+
+    /// Connects the ring starting at vertex `ring` with the vertex `apex`,
+    /// which is above it unless `below`. Also adds the edges of the ring.
+    const fn cap(&mut self, segments: usize, apex: usize, ring: usize, below: bool) {
+        let mut i = 0;
+        while i < segments {
+            let (here, next) = (ring + i, ring + (i + 1) % segments);
+            self.edge(apex, here);
+            self.edge(here, next);
+            if below {
+                self.face(apex, here, next);
+            } else {
+                self.face(apex, next, here);
+            }
+            i += 1;
+        }
+    }
+
+    /// Closes the ring starting at vertex `ring` with a flat disc, facing up
+    /// unless `below`. Also adds the edges of the ring.
+    const fn disc(&mut self, segments: usize, ring: usize, below: bool) {
+        let mut i = 0;
+        while i < segments {
+            self.edge(ring + i, ring + (i + 1) % segments);
+            if i >= 2 {
+                if below {
+                    self.face(ring, ring + i - 1, ring + i);
+                } else {
+                    self.face(ring, ring + i, ring + i - 1);
+                }
+            }
+            i += 1;
+        }
+    }
+
+    const fn finish(self) -> Shape3D<V, E, F> {
+        assert!(
+            self.vertices == V && self.edges == E && self.faces == F,
+            "the shape's size doesn't match its type"
+        );
+        self.shape
+    }
+}
+
+/// `(sin(x), cos(x))` for `0 <= x <= 2π`, as a `const fn`.
+const fn sin_cos(x: f32) -> (f32, f32) {
+    // Taylor series around π, where sin(π + t) = -sin(t) & cos(π + t) = -cos(t).
+    let t = x - PI;
+    let (mut sin, mut cos) = (0.0, 0.0);
+    let mut term = 1.0;
+    let mut n = 0;
+    while n < 20 {
+        match n % 4 {
+            0 => cos += term,
+            1 => sin += term,
+            2 => cos -= term,
+            _ => sin -= term,
+        }
+        n += 1;
+        term *= t / n as f32;
+    }
+    (-sin, -cos)
+}
+
+/// The square root of `x >= 0`, as a `const fn`.
+const fn sqrt(x: f32) -> f32 {
+    let mut y = if x > 1.0 { x } else { 1.0 };
+    let mut i = 0;
+    while i < 20 {
+        y = (y + x / y) / 2.0;
+        i += 1;
+    }
+    y
+}
@@ -0,0 +1,262 @@
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::Point,
+    primitives::{Line, PrimitiveStyle, Rectangle, StyledDrawable as _},
+};
+use heapless::Vec;
+use nalgebra::{Complex, ComplexField as _};
+
+/// In-place radix-2 fast fourier transform.
+///
+/// Panics if the length of `data` isn't a power of two.
+pub fn fft(data: &mut [Complex<f32>]) {
+    let n = data.len();
+    assert!(n.is_power_of_two(), "FFT length must be a power of two");
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -core::f32::consts::TAU / len as f32;
+        let w_len = Complex::new(angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let u = data[start + k];
+                let v = data[start + k + len / 2] * w;
+                data[start + k] = u + v;
+                data[start + k + len / 2] = u - v;
+                w *= w_len;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Fill `window` with a Hann window.
+///
+/// A single sample is left at 1.0, as the window would be all zero.
+pub fn hann_window(window: &mut [f32]) {
+    let n = window.len();
+    if n <= 1 {
+        window.fill(1.0);
+        return;
+    }
+    for (i, w) in window.iter_mut().enumerate() {
+        let phase = core::f32::consts::TAU * i as f32 / (n - 1) as f32;
+        *w = 0.5 * (1.0 - phase.cos());
+    }
+}
+
+/// A local maximum in a magnitude spectrum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Peak {
+    /// Frequency in Hz, interpolated between bins.
+    pub frequency: f32,
+    pub magnitude: f32,
+}
+
+/// Collects blocks of `N` samples taken at a fixed sample rate and computes
+/// their single-sided magnitude spectrum.
+///
+/// `N` has to be a power of two. The spectrum has `N / 2` bins, bin `i`
+/// covering the frequency `i * sample_rate / N`. Magnitudes are in the unit of
+/// the samples, i.e. a sine of amplitude 1 shows up as a peak of (roughly) 1.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut spectrum: Spectrum<128> = Spectrum::new(500.0);
+///
+/// loop {
+///     block!(timer.wait()).unwrap();
+///     if spectrum.push(mpu.get_acc().unwrap().norm()) {
+///         let magnitudes = spectrum.compute();
+///         // ...
+///     }
+/// }
+/// ```
+pub struct Spectrum<const N: usize> {
+    sample_rate: f32,
+    window: [f32; N],
+    window_gain: f32,
+    samples: [f32; N],
+    len: usize,
+    bins: [Complex<f32>; N],
+    magnitudes: [f32; N],
+}
+
+impl<const N: usize> Spectrum<N> {
+    pub fn new(sample_rate: f32) -> Self {
+        assert!(N.is_power_of_two(), "FFT length must be a power of two");
+
+        let mut window = [0.0; N];
+        hann_window(&mut window);
+        let window_gain = window.iter().sum::<f32>() / 2.0;
+
+        Self {
+            sample_rate,
+            window,
+            window_gain,
+            samples: [0.0; N],
+            len: 0,
+            bins: [Complex::new(0.0, 0.0); N],
+            magnitudes: [0.0; N],
+        }
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Width of one spectrum bin in Hz.
+    pub fn resolution(&self) -> f32 {
+        self.sample_rate / N as f32
+    }
+
+    /// The center frequency of spectrum bin `bin` in Hz.
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.resolution()
+    }
+
+    /// Add a sample to the current block. Returns `true` once the block is
+    /// full, further samples are ignored until [`compute`](Self::compute) is
+    /// called.
+    pub fn push(&mut self, sample: f32) -> bool {
+        if self.len < N {
+            self.samples[self.len] = sample;
+            self.len += 1;
+        }
+        self.is_full()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Compute the magnitude spectrum of the current block and start a new
+    /// one.
+    ///
+    /// The mean of the block is removed before windowing, so gravity and other
+    /// constant offsets don't leak into the low bins.
+    pub fn compute(&mut self) -> &[f32] {
+        let mean = self.samples[..self.len].iter().sum::<f32>() / self.len.max(1) as f32;
+
+        for (i, bin) in self.bins.iter_mut().enumerate() {
+            let sample = if i < self.len {
+                self.samples[i] - mean
+            } else {
+                0.0
+            };
+            *bin = Complex::new(sample * self.window[i], 0.0);
+        }
+        self.len = 0;
+
+        fft(&mut self.bins);
+
+        for (m, bin) in self.magnitudes.iter_mut().zip(&self.bins).take(N / 2) {
+            *m = bin.modulus() / self.window_gain;
+        }
+
+        self.magnitudes()
+    }
+
+    /// The magnitude spectrum computed by the last call to
+    /// [`compute`](Self::compute).
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes[..N / 2]
+    }
+
+    /// The (up to) `K` largest local maxima of the last spectrum above
+    /// `min_magnitude`, largest first. The DC bin is never reported.
+    pub fn peaks<const K: usize>(&self, min_magnitude: f32) -> Vec<Peak, K> {
+        let m = self.magnitudes();
+        let mut peaks: Vec<Peak, K> = Vec::new();
+
+        for i in 1..m.len() {
+            let (prev, here) = (m[i - 1], m[i]);
+            let next = m.get(i + 1).copied().unwrap_or(0.0);
+            if here < min_magnitude || here <= prev || here < next {
+                continue;
+            }
+
+            // Fit a parabola through the peak and its neighbours
+            let curvature = prev - 2.0 * here + next;
+            let offset = if curvature != 0.0 {
+                0.5 * (prev - next) / curvature
+            } else {
+                0.0
+            };
+            let peak = Peak {
+                frequency: (i as f32 + offset) * self.resolution(),
+                magnitude: here - 0.25 * (prev - next) * offset,
+            };
+
+            let at = peaks
+                .iter()
+                .position(|p| p.magnitude < peak.magnitude)
+                .unwrap_or(peaks.len());
+            if at < K {
+                if peaks.is_full() {
+                    peaks.pop();
+                }
+                peaks.insert(at, peak).unwrap();
+            }
+        }
+
+        peaks
+    }
+}
+
+/// Draw `magnitudes` as a bar graph into `area`, a bar reaching the top of
+/// `area` at `full_scale`.
+///
+/// If there are more bins than columns, each column shows the largest of its
+/// bins, if there are fewer, each bin is drawn as a bar several columns wide.
+pub fn draw_spectrum<D>(
+    display: &mut D,
+    area: Rectangle,
+    magnitudes: &[f32],
+    full_scale: f32,
+    bar_style: PrimitiveStyle<D::Color>,
+) where
+    D: DrawTarget,
+    D::Error: Debug,
+{
+    let width = area.size.width as usize;
+    let height = area.size.height as i32;
+    if magnitudes.is_empty() || width == 0 || height == 0 {
+        return;
+    }
+    let bins = magnitudes.len();
+    let bottom = area.top_left.y + height - 1;
+
+    for column in 0..width {
+        let from = column * bins / width;
+        let to = ((column + 1) * bins / width).max(from + 1);
+        let magnitude = magnitudes[from..to].iter().copied().fold(0.0, f32::max);
+
+        let bar = ((magnitude / full_scale).clamp(0.0, 1.0) * height as f32) as i32;
+        if bar == 0 {
+            continue;
+        }
+
+        let x = area.top_left.x + column as i32;
+        Line::new(Point::new(x, bottom), Point::new(x, bottom - bar + 1))
+            .draw_styled(&bar_style, display)
+            .unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::{GetPixel, ImageRaw},
+    pixelcolor::BinaryColor,
+    primitives::{PointsIter as _, Rectangle},
+    Pixel,
+};
+
+/// Frames of the same size laid out in one image, left to right and top to
+/// bottom, e.g. the steps of an animation.
+#[derive(Clone, Copy, Debug)]
+pub struct SpriteSheet<'a> {
+    image: ImageRaw<'a, BinaryColor>,
+    frame_size: Size,
+    columns: u32,
+    frames: usize,
+}
+
+impl<'a> SpriteSheet<'a> {
+    /// Panics unless the image is a whole number of `frame_size` frames wide &
+    /// high.
+    pub fn new(image: ImageRaw<'a, BinaryColor>, frame_size: Size) -> Self {
+        let size = image.size();
+        assert!(
+            frame_size.width > 0
+                && frame_size.height > 0
+                && size.width.is_multiple_of(frame_size.width)
+                && size.height.is_multiple_of(frame_size.height),
+            "the image must be made of whole frames"
+        );
+        let columns = size.width / frame_size.width;
+        let rows = size.height / frame_size.height;
+        Self {
+            image,
+            frame_size,
+            columns,
+            frames: (columns * rows) as usize,
+        }
+    }
+
+    /// A sheet with the whole image as its only frame.
+    pub fn single(image: ImageRaw<'a, BinaryColor>) -> Self {
+        Self::new(image, image.size())
+    }
+
+    pub fn frame_size(&self) -> Size {
+        self.frame_size
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Where frame `index` is in the image.
+    fn frame_area(&self, index: usize) -> Rectangle {
+        let index = (index % self.frames) as u32;
+        let column = index % self.columns;
+        let row = index / self.columns;
+        let top_left = Point::new(
+            (column * self.frame_size.width) as i32,
+            (row * self.frame_size.height) as i32,
+        );
+        Rectangle::new(top_left, self.frame_size)
+    }
+}
+
+/// Which pixels of a [`Sprite`] cover what's behind it.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Transparency<'a> {
+    /// All of them.
+    #[default]
+    Opaque,
+    /// Only the pixels that are on.
+    OnOnly,
+    /// The pixels that are on in this mask, laid out like the sprite sheet.
+    Mask(ImageRaw<'a, BinaryColor>),
+}
+
+/// Shows `frames` frames of a [`SpriteSheet`], starting at `first`, for
+/// `ticks` updates each, over and over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Animation {
+    pub first: usize,
+    pub frames: usize,
+    pub ticks: u32,
+}
+
+impl Animation {
+    pub const fn new(first: usize, frames: usize, ticks: u32) -> Self {
+        Self {
+            first,
+            frames,
+            ticks,
+        }
+    }
+
+    /// Only ever show `frame`.
+    pub const fn still(frame: usize) -> Self {
+        Self::new(frame, 1, 1)
+    }
+
+    /// The frame to show after `tick` updates.
+    pub fn frame(&self, tick: u32) -> usize {
+        self.first + (tick / self.ticks.max(1)) as usize % self.frames.max(1)
+    }
+}
+
+/// An animated image moving across the display.
+///
+/// Every [`update`](Self::update) advances the animation and moves the sprite
+/// by its `velocity`, bouncing it off the edges of the area it's kept in.
+#[derive(Clone, Copy, Debug)]
+pub struct Sprite<'a> {
+    sheet: SpriteSheet<'a>,
+    /// The top left corner.
+    pub position: Point,
+    /// Pixels moved per update.
+    pub velocity: Point,
+    pub animation: Animation,
+    pub transparency: Transparency<'a>,
+    tick: u32,
+}
+
+impl<'a> Sprite<'a> {
+    /// A sprite showing the first frame of `sheet` at the origin, standing
+    /// still.
+    pub fn new(sheet: SpriteSheet<'a>) -> Self {
+        Self {
+            sheet,
+            position: Point::zero(),
+            velocity: Point::zero(),
+            animation: Animation::still(0),
+            transparency: Transparency::Opaque,
+            tick: 0,
+        }
+    }
+
+    pub fn with_position(self, position: Point) -> Self {
+        Self { position, ..self }
+    }
+
+    pub fn with_velocity(self, velocity: Point) -> Self {
+        Self { velocity, ..self }
+    }
+
+    pub fn with_animation(self, animation: Animation) -> Self {
+        Self { animation, ..self }
+    }
+
+    pub fn with_transparency(self, transparency: Transparency<'a>) -> Self {
+        Self {
+            transparency,
+            ..self
+        }
+    }
+
+    /// The area the sprite covers on the display.
+    pub fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(self.position, self.sheet.frame_size)
+    }
+
+    /// Whether the sprite overlaps `other`.
+    pub fn collides_with(&self, other: &Sprite) -> bool {
+        !self
+            .bounding_box()
+            .intersection(&other.bounding_box())
+            .is_zero_sized()
+    }
+
+    /// The frame of the sprite sheet currently shown.
+    pub fn frame(&self) -> usize {
+        self.animation.frame(self.tick)
+    }
+
+    /// Advance the animation by one tick and move the sprite, bouncing off
+    /// the edges of `bounds`.
+    ///
+    /// Returns whether the sprite bounced.
+    pub fn update(&mut self, bounds: Rectangle) -> bool {
+        self.tick = self.tick.wrapping_add(1);
+
+        // The last top left corner that still keeps the sprite within bounds
+        let max = bounds.top_left + bounds.size - self.sheet.frame_size;
+        let (x, vx, bounced_x) = bounce(self.position.x, self.velocity.x, bounds.top_left.x, max.x);
+        let (y, vy, bounced_y) = bounce(self.position.y, self.velocity.y, bounds.top_left.y, max.y);
+
+        self.position = Point::new(x, y);
+        self.velocity = Point::new(vx, vy);
+        bounced_x || bounced_y
+    }
+
+    pub fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let area = self.sheet.frame_area(self.frame());
+        let offset = self.position - area.top_left;
+
+        display.draw_iter(area.points().filter_map(|p| {
+            let color = self.sheet.image.pixel(p)?;
+            let visible = match &self.transparency {
+                Transparency::Opaque => true,
+                Transparency::OnOnly => color.is_on(),
+                Transparency::Mask(mask) => mask.pixel(p).is_some_and(|m| m.is_on()),
+            };
+            visible.then_some(Pixel(p + offset, color))
+        }))
+    }
+}
+
+/// Move `position` by `velocity` along one axis, bouncing off `min` & `max`.
+fn bounce(position: i32, velocity: i32, min: i32, max: i32) -> (i32, i32, bool) {
+    if max <= min {
+        // Doesn't fit at all
+        return (min, velocity, false);
+    }
+
+    // Reflected once, and kept inside when moving further than from `min`
+    // to `max` in one go
+    let next = position + velocity;
+    if next < min {
+        ((2 * min - next).min(max), -velocity, true)
+    } else if next > max {
+        ((2 * max - next).max(min), -velocity, true)
+    } else {
+        (next, velocity, false)
+    }
+}
@@ -0,0 +1,182 @@
+use nalgebra::Vector3;
+
+/// Parameters for [`StepCounter`].
+///
+/// Accelerations are in g, frequencies in Hz and durations in seconds.
+#[derive(Clone, Copy, Debug)]
+pub struct StepConfig {
+    /// Cutoff of the low-pass filter smoothing the acceleration magnitude.
+    pub smoothing_cutoff: f32,
+    /// Cutoff of the (much slower) low-pass filter tracking gravity, which is
+    /// subtracted from the smoothed magnitude.
+    pub gravity_cutoff: f32,
+    /// The threshold never drops below this, so standing still doesn't count
+    /// steps from sensor noise.
+    pub min_threshold: f32,
+    /// The threshold as a fraction of the recent peak amplitude.
+    pub threshold_ratio: f32,
+    /// Time for the recent peak amplitude to decay back to zero.
+    pub envelope_decay: f32,
+    /// Shortest time between two steps, anything faster is a bounce of the
+    /// previous step.
+    pub min_interval: f32,
+    /// Longest time between two steps of the same walk. After a longer pause
+    /// the cadence starts over.
+    pub max_interval: f32,
+}
+
+impl Default for StepConfig {
+    fn default() -> Self {
+        Self {
+            smoothing_cutoff: 4.0,
+            gravity_cutoff: 0.2,
+            min_threshold: 0.08,
+            threshold_ratio: 0.5,
+            envelope_decay: 2.0,
+            min_interval: 0.25,
+            max_interval: 2.0,
+        }
+    }
+}
+
+/// Counts steps from the accelerometer magnitude.
+///
+/// Every step shows up as a peak in the magnitude of the acceleration. The
+/// magnitude is smoothed, gravity is removed and peaks that rise above an
+/// adaptive threshold (following the amplitude of the recent peaks) are
+/// counted as steps, as long as they aren't closer together than
+/// [`StepConfig::min_interval`].
+///
+/// ## Example
+///
+/// ```rs
+/// let mut steps = StepCounter::new(StepConfig::default());
+///
+/// loop {
+///     steps.update(mpu.get_acc().unwrap(), dt);
+///
+///     hprintln!("{} steps at {:.0} steps/min", steps.steps(), steps.cadence());
+/// }
+/// ```
+pub struct StepCounter {
+    config: StepConfig,
+    filter: Option<(f32, f32)>,
+    envelope: f32,
+    peak: Option<f32>,
+    since_step: f32,
+    walking: bool,
+    steps: u32,
+    cadence: f32,
+}
+
+impl StepCounter {
+    pub fn new(config: StepConfig) -> Self {
+        Self {
+            config,
+            filter: None,
+            envelope: 0.0,
+            peak: None,
+            since_step: 0.0,
+            walking: false,
+            steps: 0,
+            cadence: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &StepConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut StepConfig {
+        &mut self.config
+    }
+
+    /// Number of steps counted so far.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Current cadence in steps per minute, `0.0` when not walking.
+    pub fn cadence(&self) -> f32 {
+        self.cadence
+    }
+
+    /// Reset the step count and cadence.
+    pub fn reset(&mut self) {
+        self.steps = 0;
+        self.cadence = 0.0;
+        self.walking = false;
+    }
+
+    /// Process one reading, with `acc` in g and `dt` the time in seconds since
+    /// the previous reading. Returns `true` if a step was counted.
+    pub fn update(&mut self, acc: Vector3<f32>, dt: f32) -> bool {
+        let magnitude = acc.norm();
+
+        let (smoothed, gravity) = match self.filter {
+            None => (magnitude, magnitude),
+            Some((smoothed, gravity)) => (
+                smoothed + low_pass(dt, self.config.smoothing_cutoff) * (magnitude - smoothed),
+                gravity + low_pass(dt, self.config.gravity_cutoff) * (magnitude - gravity),
+            ),
+        };
+        self.filter = Some((smoothed, gravity));
+        let signal = smoothed - gravity;
+
+        self.since_step += dt;
+        if self.since_step > self.config.max_interval {
+            self.walking = false;
+            self.cadence = 0.0;
+        }
+
+        self.envelope -= self.envelope * (dt / self.config.envelope_decay).min(1.0);
+        let threshold =
+            (self.envelope * self.config.threshold_ratio).max(self.config.min_threshold);
+
+        match self.peak {
+            None => {
+                if signal > threshold {
+                    self.peak = Some(signal);
+                }
+                false
+            }
+            Some(peak) if signal > threshold => {
+                self.peak = Some(peak.max(signal));
+                false
+            }
+            Some(peak) => {
+                // The peak is over
+                self.peak = None;
+                self.envelope = self.envelope.max(peak);
+                self.step()
+            }
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        if self.since_step < self.config.min_interval {
+            return false;
+        }
+
+        if self.walking {
+            let cadence = 60.0 / self.since_step;
+            self.cadence = if self.cadence == 0.0 {
+                cadence
+            } else {
+                self.cadence + 0.25 * (cadence - self.cadence)
+            };
+        }
+
+        self.walking = true;
+        self.since_step = 0.0;
+        self.steps += 1;
+        true
+    }
+}
+
+/// The smoothing factor of a first order low-pass filter with cutoff `cutoff`
+/// sampled every `dt` seconds.
+fn low_pass(dt: f32, cutoff: f32) -> f32 {
+    let rc = 1.0 / (core::f32::consts::TAU * cutoff);
+    dt / (rc + dt)
+}
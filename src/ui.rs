@@ -0,0 +1,1016 @@
+//! Widgets for small monochrome displays, driven by [`Key`]s.
+//!
+//! Every [`Widget`] draws itself into the area it's given and reacts to the
+//! keys sent to it while it has the focus. A [`Form`] stacks widgets in rows
+//! and moves the focus between them with [`Key::Up`] & [`Key::Down`]; a
+//! [`MessageBox`] is drawn on top of everything else and takes all the keys
+//! until it's closed.
+
+use core::fmt::Write as _;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Angle, Point, Size},
+    mono_font::{MonoFont, MonoTextStyle},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Arc, Line, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use heapless::String;
+// With std, `f32` has its own `sqrt` etc
+#[cfg(not(feature = "std"))]
+use nalgebra::ComplexField as _;
+
+use crate::input::Key;
+
+/// How the widgets look.
+#[derive(Clone, Copy, Debug)]
+pub struct Theme<'a> {
+    pub font: &'a MonoFont<'a>,
+    /// Pixels between a row's text and its edges.
+    pub padding: u32,
+}
+
+impl<'a> Theme<'a> {
+    pub const fn new(font: &'a MonoFont<'a>) -> Self {
+        Self { font, padding: 1 }
+    }
+
+    /// The height of a row of text.
+    pub fn line_height(&self) -> u32 {
+        self.font.character_size.height + 2 * self.padding
+    }
+
+    /// Draw `text` into `row`, white on black, or black on white if
+    /// `highlight`ed.
+    fn draw_row<D>(
+        &self,
+        display: &mut D,
+        row: Rectangle,
+        text: &str,
+        alignment: Alignment,
+        highlight: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let color = if highlight {
+            row.into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(display)?;
+            BinaryColor::Off
+        } else {
+            BinaryColor::On
+        };
+        self.draw_text(display, row, text, alignment, color)
+    }
+
+    fn draw_text<D>(
+        &self,
+        display: &mut D,
+        row: Rectangle,
+        text: &str,
+        alignment: Alignment,
+        color: BinaryColor,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let padding = self.padding as i32;
+        let x = match alignment {
+            Alignment::Left => row.top_left.x + padding,
+            Alignment::Center => row.center().x,
+            Alignment::Right => row.top_left.x + row.size.width as i32 - 1 - padding,
+        };
+        let style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(Baseline::Top)
+            .build();
+        Text::with_text_style(
+            text,
+            Point::new(x, row.top_left.y + padding),
+            MonoTextStyle::new(self.font, color),
+            style,
+        )
+        .draw(display)?;
+        Ok(())
+    }
+}
+
+/// What a [`Widget`] did with a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    /// Nothing, the key is for someone else.
+    Ignored,
+    /// Its value or what it shows changed.
+    Changed,
+    /// It was chosen, e.g. a menu item or a button.
+    Activated,
+    /// It was dismissed.
+    Closed,
+}
+
+/// Something drawn on the display that can take the focus & react to keys.
+pub trait Widget {
+    /// How many pixels high the widget wants to be.
+    fn height(&self, theme: &Theme) -> u32 {
+        theme.line_height()
+    }
+
+    /// Whether the widget can take the focus, i.e. reacts to keys.
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn handle(&mut self, _key: Key) -> Response {
+        Response::Ignored
+    }
+
+    /// Draw the widget into `area`, highlighted if it has the focus.
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>;
+}
+
+/// How much of the available space to take in [`rows`] & [`columns`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Length {
+    /// That many pixels.
+    Fixed(u32),
+    /// An equal share of what's left after the fixed lengths.
+    Fill,
+}
+
+/// Split `area` into rows, from top to bottom.
+pub fn rows<const N: usize>(area: Rectangle, heights: [Length; N]) -> [Rectangle; N] {
+    let mut y = area.top_left.y;
+    split(area.size.height, heights).map(|height| {
+        let row = Rectangle::new(
+            Point::new(area.top_left.x, y),
+            Size::new(area.size.width, height),
+        );
+        y += height as i32;
+        row
+    })
+}
+
+/// Split `area` into columns, from left to right.
+pub fn columns<const N: usize>(area: Rectangle, widths: [Length; N]) -> [Rectangle; N] {
+    let mut x = area.top_left.x;
+    split(area.size.width, widths).map(|width| {
+        let column = Rectangle::new(
+            Point::new(x, area.top_left.y),
+            Size::new(width, area.size.height),
+        );
+        x += width as i32;
+        column
+    })
+}
+
+fn split<const N: usize>(total: u32, lengths: [Length; N]) -> [u32; N] {
+    let fixed: u32 = lengths
+        .iter()
+        .map(|l| match l {
+            Length::Fixed(n) => *n,
+            Length::Fill => 0,
+        })
+        .sum();
+    let fills = lengths.iter().filter(|l| **l == Length::Fill).count() as u32;
+    let mut rest = total.saturating_sub(fixed);
+
+    let mut fill = 0;
+    lengths.map(|l| match l {
+        Length::Fixed(n) => n,
+        Length::Fill => {
+            // The last one gets what doesn't divide evenly
+            fill += 1;
+            let share = if fill == fills {
+                rest
+            } else {
+                rest / (fills - fill + 1)
+            };
+            rest -= share;
+            share
+        }
+    })
+}
+
+/// A line of text that can't take the focus, e.g. a heading.
+#[derive(Clone, Copy, Debug)]
+pub struct Caption<'a> {
+    pub text: &'a str,
+    pub alignment: Alignment,
+    /// Drawn black on white.
+    pub inverted: bool,
+}
+
+impl<'a> Caption<'a> {
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            alignment: Alignment::Left,
+            inverted: false,
+        }
+    }
+
+    /// A centered, inverted caption.
+    pub const fn title(text: &'a str) -> Self {
+        Self {
+            text,
+            alignment: Alignment::Center,
+            inverted: true,
+        }
+    }
+}
+
+impl Widget for Caption<'_> {
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        _: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        theme.draw_row(display, area, self.text, self.alignment, self.inverted)
+    }
+}
+
+/// Items to choose from, one per row.
+///
+/// Up & down select the previous/next item, select or a digit key (for the
+/// first 9 items) activates it.
+#[derive(Clone, Copy, Debug)]
+pub struct Menu<'a> {
+    pub items: &'a [&'a str],
+    pub selected: usize,
+    /// How many items are shown at a time.
+    pub rows: usize,
+}
+
+impl<'a> Menu<'a> {
+    pub const fn new(items: &'a [&'a str], rows: usize) -> Self {
+        Self {
+            items,
+            selected: 0,
+            rows,
+        }
+    }
+}
+
+impl Widget for Menu<'_> {
+    fn height(&self, theme: &Theme) -> u32 {
+        self.rows as u32 * theme.line_height()
+    }
+
+    fn handle(&mut self, key: Key) -> Response {
+        match key {
+            Key::Up if self.selected > 0 => {
+                self.selected -= 1;
+                Response::Changed
+            }
+            Key::Down if self.selected + 1 < self.items.len() => {
+                self.selected += 1;
+                Response::Changed
+            }
+            Key::Select if !self.items.is_empty() => Response::Activated,
+            Key::Digit(d @ 1..=9) if (d as usize) <= self.items.len() => {
+                self.selected = d as usize - 1;
+                Response::Activated
+            }
+            _ => Response::Ignored,
+        }
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        // Keep the selected item in view
+        let first = (self.selected + 1).saturating_sub(self.rows);
+        let area = draw_scrollbar(display, area, first, self.rows, self.items.len())?;
+
+        let line_height = theme.line_height();
+        for (i, item) in self.items.iter().enumerate().skip(first).take(self.rows) {
+            let row = Rectangle::new(
+                area.top_left + Point::new(0, ((i - first) as u32 * line_height) as i32),
+                Size::new(area.size.width, line_height),
+            );
+            let selected = i == self.selected;
+            theme.draw_row(display, row, item, Alignment::Left, selected && focused)?;
+            if selected && !focused {
+                row.into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+                    .draw(display)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lines of text to scroll through with up & down, e.g. a log or help text.
+#[derive(Clone, Copy, Debug)]
+pub struct List<'a> {
+    pub lines: &'a [&'a str],
+    /// The first line shown.
+    pub offset: usize,
+    /// How many lines are shown at a time.
+    pub rows: usize,
+}
+
+impl<'a> List<'a> {
+    pub const fn new(lines: &'a [&'a str], rows: usize) -> Self {
+        Self {
+            lines,
+            offset: 0,
+            rows,
+        }
+    }
+}
+
+impl Widget for List<'_> {
+    fn height(&self, theme: &Theme) -> u32 {
+        self.rows as u32 * theme.line_height()
+    }
+
+    fn handle(&mut self, key: Key) -> Response {
+        match key {
+            Key::Up if self.offset > 0 => {
+                self.offset -= 1;
+                Response::Changed
+            }
+            Key::Down if self.offset + self.rows < self.lines.len() => {
+                self.offset += 1;
+                Response::Changed
+            }
+            _ => Response::Ignored,
+        }
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let area = draw_scrollbar(display, area, self.offset, self.rows, self.lines.len())?;
+        if focused {
+            Line::new(
+                area.top_left,
+                area.top_left + Point::new(0, area.size.height as i32 - 1),
+            )
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(display)?;
+        }
+
+        let [_, text] = columns(area, [Length::Fixed(2), Length::Fill]);
+        let rows = text.rows().step_by(theme.line_height() as usize);
+        for (line, y) in self
+            .lines
+            .iter()
+            .skip(self.offset)
+            .take(self.rows)
+            .zip(rows)
+        {
+            let row = Rectangle::new(
+                Point::new(text.top_left.x, y),
+                Size::new(text.size.width, theme.line_height()),
+            );
+            theme.draw_row(display, row, line, Alignment::Left, false)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draw a scroll bar along the right edge of `area` if there are more than
+/// `shown` rows, and return what's left of `area`.
+fn draw_scrollbar<D>(
+    display: &mut D,
+    area: Rectangle,
+    first: usize,
+    shown: usize,
+    total: usize,
+) -> Result<Rectangle, D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    if total <= shown {
+        return Ok(area);
+    }
+
+    let [rest, bar] = columns(area, [Length::Fill, Length::Fixed(2)]);
+    let height = bar.size.height as usize;
+    let thumb = Rectangle::new(
+        bar.top_left + Point::new(0, (first * height / total) as i32),
+        Size::new(bar.size.width, (shown * height / total).max(2) as u32),
+    );
+    thumb
+        .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+        .draw(display)?;
+    Ok(rest)
+}
+
+/// A number to pick from a range.
+///
+/// Left & right step through the range; digit keys type a new value, shifting
+/// the digits in from the right.
+#[derive(Clone, Copy, Debug)]
+pub struct NumberSpinner<'a> {
+    pub label: &'a str,
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+}
+
+impl<'a> NumberSpinner<'a> {
+    pub const fn new(label: &'a str, value: i32, min: i32, max: i32) -> Self {
+        Self {
+            label,
+            value,
+            min,
+            max,
+            step: 1,
+        }
+    }
+
+    pub const fn with_step(self, step: i32) -> Self {
+        Self { step, ..self }
+    }
+
+    fn set(&mut self, value: i32) -> Response {
+        let value = value.clamp(self.min, self.max);
+        if value == self.value {
+            return Response::Ignored;
+        }
+        self.value = value;
+        Response::Changed
+    }
+}
+
+impl Widget for NumberSpinner<'_> {
+    fn handle(&mut self, key: Key) -> Response {
+        match key {
+            Key::Left => self.set(self.value.saturating_sub(self.step)),
+            Key::Right => self.set(self.value.saturating_add(self.step)),
+            Key::Digit(d) => {
+                let typed = self.value.saturating_mul(10).saturating_add(d as i32);
+                self.set(if typed <= self.max { typed } else { d as i32 })
+            }
+            _ => Response::Ignored,
+        }
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let mut value: String<16> = String::new();
+        let _ = if focused {
+            write!(value, "<{}>", self.value)
+        } else {
+            write!(value, "{}", self.value)
+        };
+
+        theme.draw_row(display, area, self.label, Alignment::Left, focused)?;
+        let color = if focused {
+            BinaryColor::Off
+        } else {
+            BinaryColor::On
+        };
+        theme.draw_text(display, area, &value, Alignment::Right, color)
+    }
+}
+
+/// An option to turn on or off with select.
+#[derive(Clone, Copy, Debug)]
+pub struct Checkbox<'a> {
+    pub label: &'a str,
+    pub checked: bool,
+}
+
+impl<'a> Checkbox<'a> {
+    pub const fn new(label: &'a str, checked: bool) -> Self {
+        Self { label, checked }
+    }
+}
+
+impl Widget for Checkbox<'_> {
+    fn handle(&mut self, key: Key) -> Response {
+        match key {
+            Key::Select => {
+                self.checked = !self.checked;
+                Response::Changed
+            }
+            _ => Response::Ignored,
+        }
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let color = if focused {
+            BinaryColor::Off
+        } else {
+            BinaryColor::On
+        };
+        theme.draw_row(display, area, self.label, Alignment::Left, focused)?;
+
+        let size = theme
+            .font
+            .character_size
+            .height
+            .min(area.size.height)
+            .saturating_sub(2);
+        if size == 0 {
+            // Too short for a box
+            return Ok(());
+        }
+        let right = area.top_left.x + area.size.width as i32 - 1 - theme.padding as i32;
+        let b = Rectangle::new(
+            Point::new(right - size as i32 + 1, area.center().y - size as i32 / 2),
+            Size::new_equal(size),
+        );
+        b.into_styled(PrimitiveStyle::with_stroke(color, 1))
+            .draw(display)?;
+        if self.checked {
+            b.offset(-2)
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(display)?;
+        }
+        Ok(())
+    }
+}
+
+/// How far along something is, as a bar filling up from the left.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressBar {
+    pub value: u32,
+    pub max: u32,
+}
+
+impl ProgressBar {
+    pub const fn new(max: u32) -> Self {
+        Self { value: 0, max }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        _: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let bar = area.offset(-(theme.padding as i32));
+        bar.into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(display)?;
+
+        let inner = bar.offset(-2);
+        let width = if self.max == 0 {
+            0
+        } else {
+            (inner.size.width as u64 * self.value.min(self.max) as u64 / self.max as u64) as u32
+        };
+        Rectangle::new(inner.top_left, Size::new(width, inner.size.height))
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(display)
+    }
+}
+
+/// A value within a range, as a needle on a half circle dial with the value
+/// below it.
+#[derive(Clone, Copy, Debug)]
+pub struct Gauge<'a> {
+    pub label: &'a str,
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+    /// In pixels, including the text.
+    pub height: u32,
+}
+
+impl<'a> Gauge<'a> {
+    pub const fn new(label: &'a str, min: i32, max: i32) -> Self {
+        Self {
+            label,
+            value: min,
+            min,
+            max,
+            height: 32,
+        }
+    }
+
+    pub const fn with_height(self, height: u32) -> Self {
+        Self { height, ..self }
+    }
+}
+
+impl Widget for Gauge<'_> {
+    fn height(&self, _: &Theme) -> u32 {
+        self.height
+    }
+
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        _: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let [dial, text] = rows(area, [Length::Fill, Length::Fixed(theme.line_height())]);
+        let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+
+        let radius = (dial.size.width / 2)
+            .min(dial.size.height)
+            .saturating_sub(1);
+        let center = Point::new(dial.center().x, dial.top_left.y + radius as i32);
+        Arc::with_center(
+            center,
+            2 * radius + 1,
+            Angle::from_degrees(180.0),
+            Angle::from_degrees(180.0),
+        )
+        .into_styled(style)
+        .draw(display)?;
+
+        let range = (self.max - self.min).max(1) as f32;
+        let fraction = ((self.value - self.min) as f32 / range).clamp(0.0, 1.0);
+        let (sin, cos) = (core::f32::consts::PI * (1.0 - fraction)).sin_cos();
+        let length = radius.saturating_sub(2) as f32;
+        let tip = center + Point::new((cos * length) as i32, -(sin * length) as i32);
+        Line::new(center, tip).into_styled(style).draw(display)?;
+
+        let mut value: String<24> = String::new();
+        let _ = write!(value, "{} {}", self.label, self.value);
+        theme.draw_row(display, text, &value, Alignment::Center, false)
+    }
+}
+
+/// A framed message with a title and buttons to choose from with left &
+/// right, e.g. to confirm something.
+///
+/// Select [`Activated`](Response::Activated) the selected button, back
+/// [`Closed`](Response::Closed) the box without choosing any. Drawn into the
+/// middle of the area it's given, on top of what's already there.
+#[derive(Clone, Copy, Debug)]
+pub struct MessageBox<'a> {
+    pub title: &'a str,
+    /// Can be several lines.
+    pub text: &'a str,
+    pub buttons: &'a [&'a str],
+    pub selected: usize,
+}
+
+impl<'a> MessageBox<'a> {
+    pub const fn new(title: &'a str, text: &'a str, buttons: &'a [&'a str]) -> Self {
+        Self {
+            title,
+            text,
+            buttons,
+            selected: 0,
+        }
+    }
+}
+
+impl Widget for MessageBox<'_> {
+    fn height(&self, theme: &Theme) -> u32 {
+        let lines = self.text.lines().count() as u32 + 1 + !self.buttons.is_empty() as u32;
+        lines * theme.line_height() + 2
+    }
+
+    fn handle(&mut self, key: Key) -> Response {
+        match key {
+            Key::Left if self.selected > 0 => {
+                self.selected -= 1;
+                Response::Changed
+            }
+            Key::Right if self.selected + 1 < self.buttons.len() => {
+                self.selected += 1;
+                Response::Changed
+            }
+            Key::Select => Response::Activated,
+            Key::Back => Response::Closed,
+            _ => Response::Ignored,
+        }
+    }
+
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        _: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let size = Size::new(
+            area.size.width.saturating_sub(8),
+            self.height(theme).min(area.size.height),
+        );
+        let frame = Rectangle::with_center(area.center(), size);
+        frame
+            .into_styled(
+                PrimitiveStyleBuilder::new()
+                    .stroke_color(BinaryColor::On)
+                    .stroke_width(1)
+                    .fill_color(BinaryColor::Off)
+                    .build(),
+            )
+            .draw(display)?;
+
+        let line_height = theme.line_height();
+        let inner = frame.offset(-1);
+        let [title, text, buttons] = rows(
+            inner,
+            [
+                Length::Fixed(line_height),
+                Length::Fill,
+                Length::Fixed(if self.buttons.is_empty() {
+                    0
+                } else {
+                    line_height
+                }),
+            ],
+        );
+        theme.draw_row(display, title, self.title, Alignment::Center, true)?;
+        for (line, y) in self
+            .text
+            .lines()
+            .zip(text.rows().step_by(line_height as usize))
+        {
+            let row = Rectangle::new(
+                Point::new(text.top_left.x, y),
+                Size::new(text.size.width, line_height),
+            );
+            theme.draw_row(display, row, line, Alignment::Center, false)?;
+        }
+
+        let count = self.buttons.len().max(1) as u32;
+        let width = buttons.size.width / count;
+        for (i, label) in self.buttons.iter().enumerate() {
+            let button = Rectangle::new(
+                buttons.top_left + Point::new((i as u32 * width) as i32, 0),
+                Size::new(width, line_height),
+            );
+            theme.draw_row(
+                display,
+                button,
+                label,
+                Alignment::Center,
+                i == self.selected,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A fixed set of widgets of different types, i.e. a tuple of up to 8
+/// [`Widget`]s, accessed by index.
+pub trait Widgets {
+    fn count(&self) -> usize;
+
+    fn height(&self, index: usize, theme: &Theme) -> u32;
+
+    fn focusable(&self, index: usize) -> bool;
+
+    fn handle(&mut self, index: usize, key: Key) -> Response;
+
+    fn draw<D>(
+        &self,
+        index: usize,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>;
+}
+
+macro_rules! impl_widgets {
+    ($($w:ident $i:tt),+) => {
+        impl<$($w: Widget),+> Widgets for ($($w,)+) {
+            fn count(&self) -> usize {
+                [$($i),+].len()
+            }
+
+            fn height(&self, index: usize, theme: &Theme) -> u32 {
+                match index {
+                    $($i => self.$i.height(theme),)+
+                    _ => 0,
+                }
+            }
+
+            fn focusable(&self, index: usize) -> bool {
+                match index {
+                    $($i => self.$i.focusable(),)+
+                    _ => false,
+                }
+            }
+
+            fn handle(&mut self, index: usize, key: Key) -> Response {
+                match index {
+                    $($i => self.$i.handle(key),)+
+                    _ => Response::Ignored,
+                }
+            }
+
+            fn draw<D>(
+                &self,
+                index: usize,
+                display: &mut D,
+                area: Rectangle,
+                theme: &Theme,
+                focused: bool,
+            ) -> Result<(), D::Error>
+            where
+                D: DrawTarget<Color = BinaryColor>,
+            {
+                match index {
+                    $($i => self.$i.draw(display, area, theme, focused),)+
+                    _ => Ok(()),
+                }
+            }
+        }
+    };
+}
+
+impl_widgets!(A 0);
+impl_widgets!(A 0, B 1);
+impl_widgets!(A 0, B 1, C 2);
+impl_widgets!(A 0, B 1, C 2, E 3);
+impl_widgets!(A 0, B 1, C 2, E 3, F 4);
+impl_widgets!(A 0, B 1, C 2, E 3, F 4, G 5);
+impl_widgets!(A 0, B 1, C 2, E 3, F 4, G 5, H 6);
+impl_widgets!(A 0, B 1, C 2, E 3, F 4, G 5, H 6, I 7);
+
+/// [`Widgets`] stacked in rows, with the focus on one of them.
+///
+/// Keys go to the focused widget first; up & down keys it ignores move the
+/// focus to the previous/next widget that can take it.
+///
+/// ## Example
+///
+/// ```rs
+/// let mut form = Form::new((
+///     Caption::title("Settings"),
+///     NumberSpinner::new("Contrast", 8, 0, 15),
+///     Checkbox::new("Invert", false),
+/// ));
+///
+/// if form.handle(key) == Response::Changed {
+///     let (_, contrast, invert) = form.widgets();
+///     // ...
+/// }
+/// let area = display.bounding_box();
+/// form.draw(&mut display, area, &theme, true).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Form<W> {
+    widgets: W,
+    focus: usize,
+}
+
+impl<W: Widgets> Form<W> {
+    /// A form with the focus on the first widget that can take it.
+    pub fn new(widgets: W) -> Self {
+        let focus = (0..widgets.count())
+            .find(|&i| widgets.focusable(i))
+            .unwrap_or(0);
+        Self { widgets, focus }
+    }
+
+    pub fn widgets(&self) -> &W {
+        &self.widgets
+    }
+
+    pub fn widgets_mut(&mut self) -> &mut W {
+        &mut self.widgets
+    }
+
+    /// The index of the focused widget.
+    pub fn focus(&self) -> usize {
+        self.focus
+    }
+
+    /// Move the focus to the widget at `index`, if it can take it.
+    pub fn set_focus(&mut self, index: usize) {
+        if self.widgets.focusable(index) {
+            self.focus = index;
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) -> Response {
+        let mut i = self.focus;
+        loop {
+            i = match forward {
+                true if i + 1 < self.widgets.count() => i + 1,
+                false if i > 0 => i - 1,
+                _ => return Response::Ignored,
+            };
+            if self.widgets.focusable(i) {
+                self.focus = i;
+                return Response::Changed;
+            }
+        }
+    }
+}
+
+impl<W: Widgets> Widget for Form<W> {
+    fn height(&self, theme: &Theme) -> u32 {
+        (0..self.widgets.count())
+            .map(|i| self.widgets.height(i, theme))
+            .sum()
+    }
+
+    fn focusable(&self) -> bool {
+        (0..self.widgets.count()).any(|i| self.widgets.focusable(i))
+    }
+
+    fn handle(&mut self, key: Key) -> Response {
+        match self.widgets.handle(self.focus, key) {
+            Response::Ignored => match key {
+                Key::Up => self.move_focus(false),
+                Key::Down => self.move_focus(true),
+                _ => Response::Ignored,
+            },
+            response => response,
+        }
+    }
+
+    /// Widgets that don't fit into `area` are cut off.
+    fn draw<D>(
+        &self,
+        display: &mut D,
+        area: Rectangle,
+        theme: &Theme,
+        focused: bool,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let mut display = display.clipped(&area);
+        let mut y = area.top_left.y;
+        for i in 0..self.widgets.count() {
+            let height = self.widgets.height(i, theme);
+            let row = Rectangle::new(
+                Point::new(area.top_left.x, y),
+                Size::new(area.size.width, height),
+            );
+            self.widgets
+                .draw(i, &mut display, row, theme, focused && i == self.focus)?;
+            y += height as i32;
+        }
+        Ok(())
+    }
+}